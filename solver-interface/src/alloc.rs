@@ -0,0 +1,121 @@
+use bincode::{Decode, Encode};
+
+/// Heap usage of one run of a part. Only collected when the `alloc-stats`
+/// feature is enabled, which installs a counting global allocator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Decode, Encode)]
+pub struct AllocStats {
+    /// Most heap memory in use at once, not counting what was already in use
+    /// before the part started.
+    pub peak_bytes: u64,
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total bytes requested across all allocations.
+    pub allocated_bytes: u64,
+}
+
+/// Runs `f`, counting its allocations if the counting allocator is installed.
+pub(crate) fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let start = counting::start();
+        let t = f();
+        (t, Some(counting::finish(start)))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use super::AllocStats;
+
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    /// The system allocator, keeping track of how much it's been used.
+    struct Counting;
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED.fetch_add(size, Relaxed);
+        let current = CURRENT.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(current, Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT.fetch_sub(size as u64, Relaxed);
+    }
+
+    // SAFETY: Every method forwards to `System` unchanged.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: Same contract as this method.
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            // SAFETY: Same contract as this method.
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            // SAFETY: Same contract as this method.
+            unsafe { System.dealloc(ptr, layout) };
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            // SAFETY: Same contract as this method.
+            let new = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new
+        }
+    }
+
+    /// Counters at the start of a measurement.
+    pub(super) struct Start {
+        current: u64,
+        allocations: u64,
+        allocated: u64,
+    }
+
+    pub(super) fn start() -> Start {
+        let current = CURRENT.load(Relaxed);
+        PEAK.store(current, Relaxed);
+        Start {
+            current,
+            allocations: ALLOCATIONS.load(Relaxed),
+            allocated: ALLOCATED.load(Relaxed),
+        }
+    }
+
+    pub(super) fn finish(start: Start) -> AllocStats {
+        AllocStats {
+            peak_bytes: PEAK.load(Relaxed).saturating_sub(start.current),
+            allocations: ALLOCATIONS.load(Relaxed) - start.allocations,
+            allocated_bytes: ALLOCATED.load(Relaxed) - start.allocated,
+        }
+    }
+}
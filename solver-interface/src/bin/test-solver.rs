@@ -0,0 +1,36 @@
+//! A solver that misbehaves on purpose, for the tests of `ParentSolver`.
+//!
+//! Part 1 never finishes, part 2 panics, and part 3 allocates more than the
+//! tests' memory limit.
+
+use std::fmt::Display;
+use std::hint::black_box;
+use std::time::Duration;
+
+use solver_interface::{ChildSolver, ChildSolverExt};
+
+struct Solver;
+
+impl ChildSolver for Solver {
+    fn part_one(_input: &[u8], _debug: u8) -> impl Display + 'static {
+        loop {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        #[allow(unreachable_code)]
+        ""
+    }
+
+    fn part_two(input: &[u8], _debug: u8) -> impl Display + 'static {
+        panic!("bad input {:?}", String::from_utf8_lossy(input));
+        #[allow(unreachable_code)]
+        ""
+    }
+
+    fn run_any(_input: &[u8], _part: u32, _debug: u8) -> impl Display + 'static {
+        black_box(vec![1u8; 1 << 30]).len()
+    }
+}
+
+fn main() {
+    Solver::run().unwrap();
+}
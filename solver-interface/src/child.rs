@@ -1,21 +1,55 @@
 use super::{
-    bincode_config, time_fn, Answer, Bench, BenchResult, ChildToParent, ParentToChild, Run,
-    SolverError,
+    AllocStats, Answer, Bench, BenchParse, BenchResult, ChildToParent, Initialization,
+    OUT_OF_MEMORY_EXIT_CODE, Panic, ParentToChild, Run, SolverError, alloc, bincode_config,
+    time_fn,
 };
 
 use bincode::{decode_from_reader, encode_into_std_write, error::DecodeError};
 
+use std::backtrace::{Backtrace, BacktraceStatus};
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdin, stdout, BufReader, BufWriter, Write};
+use std::io::{BufReader, BufWriter, Write, stdin, stdout};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Once;
 use std::time::Duration;
 
 /// A type that can accept instructions from a parent process on which parts
 /// and inputs to run.
+///
+/// Solvers either implement `part_one` and `part_two`, which parse the input
+/// themselves, or implement `parse`, `part_one_parsed`, and `part_two_parsed`
+/// so that parsing is only done once and can be timed on its own.
 pub trait ChildSolver {
-    fn part_one(input: &[u8], _debug: u8) -> impl Display + 'static;
-    fn part_two(input: &[u8], _debug: u8) -> impl Display + 'static;
+    /// The input after [`ChildSolver::parse`].
+    type Parsed = ();
+
+    /// Parses the input once before any part runs. Returns `None` by default,
+    /// which means the parts are given the raw input instead.
+    fn parse(_input: &[u8], _debug: u8) -> Option<Self::Parsed> {
+        None
+    }
+
+    fn part_one(input: &[u8], debug: u8) -> impl Display + 'static {
+        let parsed = parse_or_panic::<Self>(input, debug);
+        Self::part_one_parsed(&parsed, debug).to_string()
+    }
+    fn part_two(input: &[u8], debug: u8) -> impl Display + 'static {
+        let parsed = parse_or_panic::<Self>(input, debug);
+        Self::part_two_parsed(&parsed, debug).to_string()
+    }
+    fn part_one_parsed(_parsed: &Self::Parsed, _debug: u8) -> impl Display + 'static {
+        panic!("Not implemented");
+        #[allow(unreachable_code)]
+        ""
+    }
+    fn part_two_parsed(_parsed: &Self::Parsed, _debug: u8) -> impl Display + 'static {
+        panic!("Not implemented");
+        #[allow(unreachable_code)]
+        ""
+    }
     fn run_any(_input: &[u8], _part: u32, _debug: u8) -> impl Display + 'static {
         panic!("Not implemented");
         #[allow(unreachable_code)]
@@ -23,6 +57,10 @@ pub trait ChildSolver {
     }
 }
 
+fn parse_or_panic<S: ChildSolver + ?Sized>(input: &[u8], debug: u8) -> S::Parsed {
+    S::parse(input, debug).expect("solver implements neither `parse` nor the part")
+}
+
 /// The interface used to run a `ChildSolver`.
 pub trait ChildSolverExt: ChildSolver {
     /// Starts the message receiver and sender, which calls the other
@@ -31,14 +69,17 @@ pub trait ChildSolverExt: ChildSolver {
         let mut stdin = BufReader::new(stdin());
         let mut stdout = BufWriter::new(stdout());
         let config = bincode_config();
-        let mut buf = String::new();
 
         // First message must be initialization
-        let ParentToChild::Initialize(mut init) =
+        let ParentToChild::Initialize(init) =
             decode_from_reader::<ParentToChild, _, _>(&mut stdin, config)?
         else {
             return Err(SolverError::ChildWasNotInitialized);
         };
+        apply_memory_limit(&init)?;
+        let mut state = ChildState::<Self>::new(init);
+        encode_into_std_write(ChildToParent::Initialized, &mut stdout, config)?;
+        stdout.flush()?;
 
         loop {
             let received = decode_from_reader::<ParentToChild, _, _>(&mut stdin, config);
@@ -49,26 +90,11 @@ pub trait ChildSolverExt: ChildSolver {
                 Err(e) => break Err(e.into()),
             };
 
-            let msg = match received {
-                ParentToChild::Initialize(begin) => {
-                    init = begin;
-                    continue;
-                }
-                ParentToChild::Run(run) => {
-                    let (time, answer) = run.time_solver::<Self>(&init.input, &mut buf, init.debug);
-                    ChildToParent::Answer(Answer {
-                        time,
-                        answer: answer.into(),
-                    })
-                }
-                ParentToChild::Bench(bench) => {
-                    let times = Self::bench(bench, &init.input, &mut buf, init.debug)?;
-                    ChildToParent::BenchResult(BenchResult {
-                        times,
-                        answer: Cow::Borrowed(&buf),
-                    })
-                }
-                ParentToChild::End => break Ok(()),
+            if let ParentToChild::Initialize(init) = &received {
+                apply_memory_limit(init)?;
+            }
+            let Some(msg) = state.handle(received) else {
+                break Ok(());
             };
 
             encode_into_std_write(msg, &mut stdout, config)?;
@@ -79,44 +105,268 @@ pub trait ChildSolverExt: ChildSolver {
     fn bench(
         bench: Bench,
         input: &[u8],
+        parsed: Option<&Self::Parsed>,
         buf: &mut String,
         debug: u8,
-    ) -> Result<Vec<Duration>, SolverError> {
+    ) -> Result<(Vec<Duration>, Option<AllocStats>), SolverError> {
         let Bench {
             run: Run { part },
             iters,
+            warmup,
         } = bench;
 
         let mut times = Vec::with_capacity(iters as usize);
 
-        match part {
-            1 => bench_iters(|| Self::part_one(black_box(input), debug), buf, &mut times),
-            2 => bench_iters(|| Self::part_two(black_box(input), debug), buf, &mut times),
+        let alloc = match (part, parsed) {
+            (1, Some(parsed)) => bench_iters(
+                || Self::part_one_parsed(black_box(parsed), debug),
+                buf,
+                &mut times,
+                iters,
+                warmup,
+            ),
+            (2, Some(parsed)) => bench_iters(
+                || Self::part_two_parsed(black_box(parsed), debug),
+                buf,
+                &mut times,
+                iters,
+                warmup,
+            ),
+            (1, None) => bench_iters(
+                || Self::part_one(black_box(input), debug),
+                buf,
+                &mut times,
+                iters,
+                warmup,
+            ),
+            (2, None) => bench_iters(
+                || Self::part_two(black_box(input), debug),
+                buf,
+                &mut times,
+                iters,
+                warmup,
+            ),
             _ => bench_iters(
                 || Self::run_any(black_box(input), part, debug),
                 buf,
                 &mut times,
+                iters,
+                warmup,
             ),
         }?;
-        Ok(times)
+        Ok((times, alloc))
+    }
+
+    /// Times `parse` on its own. The parsed values are thrown away.
+    fn bench_parse(bench: BenchParse, input: &[u8], debug: u8) -> Vec<Duration> {
+        let BenchParse { iters, warmup } = bench;
+
+        for _ in 0..warmup {
+            black_box(Self::parse(black_box(input), debug));
+        }
+
+        (0..iters)
+            .map(|_| time_fn(|| black_box(Self::parse(black_box(input), debug))).0)
+            .collect()
+    }
+}
+
+/// A solver's input and parsed input, answering one request at a time. This is
+/// the child's side of the protocol without the pipes, so it can also run in
+/// the parent's process.
+pub(crate) struct ChildState<S: ChildSolver + ?Sized> {
+    init: Initialization<'static>,
+    /// Set the first time a part or parse is requested after initialization
+    parsed: Option<(Duration, Option<S::Parsed>)>,
+    buf: String,
+}
+
+impl<S: ChildSolver + ?Sized> ChildState<S> {
+    pub(crate) fn new(init: Initialization<'static>) -> Self {
+        install_panic_hook();
+        Self {
+            init,
+            parsed: None,
+            buf: String::new(),
+        }
+    }
+
+    /// Responds to a message from the parent. Returns `None` when told to end.
+    pub(crate) fn handle(&mut self, received: ParentToChild<'static>) -> Option<ChildToParent<'_>> {
+        let Self { init, parsed, buf } = self;
+        let parse = || time_fn(|| S::parse(&init.input, init.debug));
+
+        let msg = match received {
+            ParentToChild::Initialize(begin) => {
+                *init = begin;
+                *parsed = None;
+                ChildToParent::Initialized
+            }
+            ParentToChild::Parse => match parse_once(parsed, parse) {
+                Ok((time, p)) => ChildToParent::Parsed(p.is_some().then_some(*time)),
+                Err(panic) => ChildToParent::Panicked(panic),
+            },
+            ParentToChild::Run(run) => {
+                let answer = parse_once(parsed, parse).and_then(|(_, p)| {
+                    catch_panic(|| run.time_solver::<S>(&init.input, p.as_ref(), buf, init.debug))
+                });
+                match answer {
+                    Ok((time, alloc, answer)) => ChildToParent::Answer(Answer {
+                        time,
+                        answer: answer.into(),
+                        alloc,
+                    }),
+                    Err(panic) => ChildToParent::Panicked(panic),
+                }
+            }
+            ParentToChild::Bench(bench) => {
+                let result = parse_once(parsed, parse).and_then(|(_, p)| {
+                    catch_panic(|| S::bench(bench, &init.input, p.as_ref(), buf, init.debug))
+                });
+                match result {
+                    Ok(Ok((times, alloc))) => ChildToParent::BenchResult(BenchResult {
+                        times,
+                        answer: Cow::Borrowed(buf),
+                        alloc,
+                    }),
+                    Ok(Err(e)) => ChildToParent::Err(e.to_string().into()),
+                    Err(panic) => ChildToParent::Panicked(panic),
+                }
+            }
+            ParentToChild::BenchParse(bench) => {
+                match catch_panic(|| S::bench_parse(bench, &init.input, init.debug)) {
+                    Ok(times) => ChildToParent::BenchResult(BenchResult {
+                        times,
+                        answer: Cow::Borrowed(""),
+                        alloc: None,
+                    }),
+                    Err(panic) => ChildToParent::Panicked(panic),
+                }
+            }
+            ParentToChild::End => return None,
+        };
+        Some(msg)
+    }
+}
+
+/// Parses the input if it hasn't been parsed yet. A panic while parsing leaves
+/// it unparsed, so the next request tries again.
+fn parse_once<P>(
+    parsed: &mut Option<(Duration, Option<P>)>,
+    parse: impl FnOnce() -> (Duration, Option<P>),
+) -> Result<&(Duration, Option<P>), Panic> {
+    if parsed.is_none() {
+        *parsed = Some(catch_panic(parse)?);
     }
+    Ok(parsed.as_ref().unwrap())
 }
 
+thread_local! {
+    /// Set while a solver runs inside [`catch_panic`] on this thread.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+
+    /// The last panic seen by the hook while [`CATCHING`] was set.
+    static LAST_PANIC: RefCell<Option<Panic>> = const { RefCell::new(None) };
+}
+
+/// Records panics inside [`catch_panic`] instead of printing them. Panics
+/// anywhere else, including threads the solver spawns, still use the previous
+/// hook.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                return previous(info);
+            }
+            let backtrace = Backtrace::capture();
+            let panic = Panic {
+                message: info.payload_as_str().unwrap_or("Box<dyn Any>").into(),
+                location: info.location().map(|l| l.to_string().into()),
+                backtrace: (backtrace.status() == BacktraceStatus::Captured)
+                    .then(|| backtrace.to_string().into()),
+            };
+            LAST_PANIC.set(Some(panic));
+        }));
+    });
+}
+
+/// Runs solver code, turning a panic into a report for the parent.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, Panic> {
+    let was_catching = CATCHING.replace(true);
+    let result = catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.map_err(|payload| {
+        LAST_PANIC.take().unwrap_or_else(|| {
+            // The panic happened on another thread and was passed along
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+            Panic {
+                message: message.into(),
+                location: None,
+                backtrace: None,
+            }
+        })
+    })
+}
+
+/// Applies the memory limit from the parent, if there is one.
+fn apply_memory_limit(init: &Initialization) -> Result<(), SolverError> {
+    if let Some(limit) = init.memory_limit {
+        limit_memory(limit)?;
+        // Allocation failures abort by default, which looks like any other crash
+        std::alloc::set_alloc_error_hook(|_| std::process::exit(OUT_OF_MEMORY_EXIT_CODE));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn limit_memory(bytes: u64) -> Result<(), SolverError> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid rlimit for the call to write to.
+    if unsafe { libc::getrlimit(libc::RLIMIT_AS, &mut limit) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    limit.rlim_cur = (bytes as libc::rlim_t).min(limit.rlim_max);
+    // SAFETY: `limit` is a valid rlimit.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_bytes: u64) -> Result<(), SolverError> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported).into())
+}
+
+/// Runs `f` untimed `warmup` times (at least once, to get the answer), then
+/// timed `iters` times, checking that every answer matches the first.
+///
+/// Returns the heap usage of the first run.
 fn bench_iters<D: Display>(
     f: impl Fn() -> D,
     buf: &mut String,
     times: &mut Vec<Duration>,
-) -> Result<(), SolverError> {
+    iters: u32,
+    warmup: u32,
+) -> Result<Option<AllocStats>, SolverError> {
     use std::fmt::Write;
 
-    let first_ans = f();
+    let (first_ans, alloc) = alloc::measure(&f);
     buf.clear();
     write!(buf, "{first_ans}").unwrap();
     let ans_len = buf.len();
 
-    for _ in 0.. {
-        let (d, ans) = time_fn(&f);
-        write!(buf, "{ans}").unwrap();
+    let check = |buf: &mut String| {
         if buf[..ans_len] != buf[ans_len..] {
             let b = buf.split_off(ans_len);
             return Err(SolverError::WrongAnswerInBench(
@@ -125,10 +375,22 @@ fn bench_iters<D: Display>(
             ));
         }
         buf.truncate(ans_len);
+        Ok(())
+    };
+
+    for _ in 1..warmup {
+        write!(buf, "{}", f()).unwrap();
+        check(buf)?;
+    }
+
+    for _ in 0..iters {
+        let (d, ans) = time_fn(&f);
+        write!(buf, "{ans}").unwrap();
+        check(buf)?;
         times.push(d);
     }
 
-    Ok(())
+    Ok(alloc)
 }
 
 impl<T: ChildSolver + ?Sized> ChildSolverExt for T {}
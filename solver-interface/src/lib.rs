@@ -1,4 +1,7 @@
+#![feature(associated_type_defaults, alloc_error_hook)]
+
 use std::borrow::Cow;
+use std::fmt::{self, Display};
 use std::time::{Duration, Instant};
 
 use bincode::config::Configuration;
@@ -6,12 +9,18 @@ use bincode::error::{DecodeError, EncodeError};
 use bincode::{Decode, Encode};
 use thiserror::Error;
 
+mod alloc;
+pub use alloc::AllocStats;
+
 mod parent;
-pub use parent::ParentSolver;
+pub use parent::{ALLOC_STATS_FEATURE, Limits, ParentSolver, PartStats, profile};
 
 mod child;
 pub use child::{ChildSolver, ChildSolverExt};
 
+/// Exit code of a child that ran out of memory under its memory limit.
+pub const OUT_OF_MEMORY_EXIT_CODE: i32 = 77;
+
 fn bincode_config() -> Configuration {
     bincode::config::standard()
 }
@@ -27,8 +36,10 @@ pub fn time_fn<F: FnOnce() -> T, T>(f: F) -> (Duration, T) {
 #[derive(Debug, Clone, Decode, Encode)]
 pub enum ParentToChild<'a> {
     Initialize(Initialization<'a>),
+    Parse,
     Run(Run),
     Bench(Bench),
+    BenchParse(BenchParse),
     End,
 }
 
@@ -36,6 +47,8 @@ pub enum ParentToChild<'a> {
 pub struct Initialization<'a> {
     pub input: Cow<'a, [u8]>,
     pub debug: u8,
+    /// Maximum address space in bytes, applied by the child to itself.
+    pub memory_limit: Option<u64>,
 }
 
 #[derive(Debug, Clone, Decode, Encode)]
@@ -44,54 +57,105 @@ pub struct Run {
 }
 
 impl Run {
-    fn time_solver<'a, S>(self, input: &[u8], buf: &'a mut String, debug: u8) -> (Duration, &'a str)
+    fn time_solver<'a, S>(
+        self,
+        input: &[u8],
+        parsed: Option<&S::Parsed>,
+        buf: &'a mut String,
+        debug: u8,
+    ) -> (Duration, Option<AllocStats>, &'a str)
     where
         S: ChildSolver + ?Sized,
     {
-        use std::fmt::Write;
         buf.clear();
 
-        let d = match self.part {
-            1 => {
-                let (d, ans) = time_fn(|| S::part_one(input, debug));
-                write!(buf, "{ans}").unwrap();
-                d
-            }
-            2 => {
-                let (d, ans) = time_fn(|| S::part_two(input, debug));
-                write!(buf, "{ans}").unwrap();
-                d
-            }
-            part => {
-                let (d, ans) = time_fn(|| S::run_any(input, part, debug));
-                write!(buf, "{ans}").unwrap();
-                d
-            }
+        let (d, alloc) = match (self.part, parsed) {
+            (1, Some(parsed)) => time_part(buf, || S::part_one_parsed(parsed, debug)),
+            (2, Some(parsed)) => time_part(buf, || S::part_two_parsed(parsed, debug)),
+            (1, None) => time_part(buf, || S::part_one(input, debug)),
+            (2, None) => time_part(buf, || S::part_two(input, debug)),
+            (part, _) => time_part(buf, || S::run_any(input, part, debug)),
         };
 
-        (d, buf.as_str())
+        (d, alloc, buf.as_str())
     }
 }
 
+/// Times one run of a part and writes its answer to `buf`.
+fn time_part<D: Display>(
+    buf: &mut String,
+    f: impl FnOnce() -> D,
+) -> (Duration, Option<AllocStats>) {
+    use std::fmt::Write;
+    let ((d, ans), alloc) = alloc::measure(|| time_fn(f));
+    write!(buf, "{ans}").unwrap();
+    (d, alloc)
+}
+
 #[derive(Debug, Clone, Decode, Encode)]
 pub struct Bench {
     pub run: Run,
     pub iters: u32,
+    /// Untimed iterations to run before the timed ones.
+    pub warmup: u32,
+}
+
+#[derive(Debug, Clone, Decode, Encode)]
+pub struct BenchParse {
+    pub iters: u32,
+    pub warmup: u32,
 }
 
 #[derive(Debug, Clone, Decode, Encode)]
 pub enum ChildToParent<'a> {
+    Initialized,
     Answer(Answer<'a>),
+    /// How long parsing took, or `None` if the solver doesn't parse separately.
+    Parsed(Option<Duration>),
     BenchResult(BenchResult<'a>),
     Err(Box<str>),
+    /// The solver panicked. The child is still running and can take more
+    /// requests.
+    Panicked(Panic),
 }
 
 impl ChildToParent<'_> {
+    /// Copies any borrowed answer so the message outlives its buffer.
+    pub fn into_owned(self) -> ChildToParent<'static> {
+        match self {
+            ChildToParent::Initialized => ChildToParent::Initialized,
+            ChildToParent::Answer(Answer {
+                answer,
+                time,
+                alloc,
+            }) => ChildToParent::Answer(Answer {
+                answer: Cow::Owned(answer.into_owned()),
+                time,
+                alloc,
+            }),
+            ChildToParent::Parsed(time) => ChildToParent::Parsed(time),
+            ChildToParent::BenchResult(BenchResult {
+                times,
+                answer,
+                alloc,
+            }) => ChildToParent::BenchResult(BenchResult {
+                times,
+                answer: Cow::Owned(answer.into_owned()),
+                alloc,
+            }),
+            ChildToParent::Err(e) => ChildToParent::Err(e),
+            ChildToParent::Panicked(panic) => ChildToParent::Panicked(panic),
+        }
+    }
+
     pub fn message_kind(&self) -> &'static str {
         match self {
+            ChildToParent::Initialized => "Initialized",
             ChildToParent::Answer(_) => "Answer",
+            ChildToParent::Parsed(_) => "Parsed",
             ChildToParent::BenchResult(_) => "BenchResult",
             ChildToParent::Err(_) => "Err",
+            ChildToParent::Panicked(_) => "Panicked",
         }
     }
 }
@@ -100,12 +164,38 @@ impl ChildToParent<'_> {
 pub struct Answer<'a> {
     answer: Cow<'a, str>,
     time: Duration,
+    alloc: Option<AllocStats>,
 }
 
 #[derive(Debug, Clone, Decode, Encode)]
 pub struct BenchResult<'a> {
     pub times: Vec<Duration>,
     pub answer: Cow<'a, str>,
+    /// Heap usage of the untimed run that produced the answer.
+    pub alloc: Option<AllocStats>,
+}
+
+/// A panic caught in the child.
+#[derive(Debug, Clone, Decode, Encode)]
+pub struct Panic {
+    pub message: Box<str>,
+    /// Where the panic happened, as `file:line:column`.
+    pub location: Option<Box<str>>,
+    /// Only captured when `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` is set.
+    pub backtrace: Option<Box<str>>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}:\n{}", self.message)?,
+            None => write!(f, "panicked:\n{}", self.message)?,
+        }
+        if let Some(backtrace) = &self.backtrace {
+            write!(f, "\nstack backtrace:\n{backtrace}")?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -123,9 +213,18 @@ pub enum SolverError {
     #[error("child was not sent an initialzation message")]
     ChildWasNotInitialized,
 
+    #[error("{0}")]
+    Panicked(Panic),
+
     #[error("child quit before sending a response")]
     ChildQuit,
 
+    #[error("child took longer than {0:?} and was stopped")]
+    Timeout(Duration),
+
+    #[error("child went over the memory limit of {0} bytes")]
+    MemoryLimit(u64),
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
@@ -1,103 +1,356 @@
 use super::bincode_config;
-use super::{Bench, BenchResult, ChildToParent, Initialization, ParentToChild, Run, SolverError};
+use super::child::ChildState;
+use super::{
+    AllocStats, Bench, BenchParse, BenchResult, ChildSolver, ChildToParent, Initialization,
+    OUT_OF_MEMORY_EXIT_CODE, ParentToChild, Run, SolverError,
+};
+use bincode::error::DecodeError;
 use bincode::{config::Configuration, decode_from_reader, encode_into_std_write};
 use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, channel};
 use std::time::Duration;
 
+type Message = Result<ChildToParent<'static>, DecodeError>;
+
+/// Feature that builds solvers with a counting allocator.
+pub const ALLOC_STATS_FEATURE: &str = "solver-interface/alloc-stats";
+
+/// The cargo profile solvers are built with.
+pub fn profile(release: bool) -> &'static str {
+    if release { "day-release" } else { "day-dev" }
+}
+
+/// Limits on what a child solver may use. The default is no limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time allowed for parsing or running a single part.
+    pub timeout: Option<Duration>,
+    /// Maximum address space of the child in bytes.
+    pub memory: Option<u64>,
+}
+
+/// How long a part took, and how much heap it used if the child counts
+/// allocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartStats {
+    pub time: Duration,
+    pub alloc: Option<AllocStats>,
+}
+
 pub struct ParentSolver {
-    process: Child,
-    stdin: BufWriter<ChildStdin>,
-    stdout: BufReader<ChildStdout>,
-    config: Configuration,
+    backend: Backend,
+    limits: Limits,
+}
+
+/// Where the solver runs.
+enum Backend {
+    /// A `cargo run` subprocess, talked to over its stdin and stdout.
+    Child {
+        process: Child,
+        stdin: BufWriter<ChildStdin>,
+        messages: Receiver<Message>,
+        config: Configuration,
+        /// Set once the child has exited or been killed.
+        dead: bool,
+    },
+    /// A solver linked into this process. Each message is handled as soon as
+    /// it's sent, and the response waits to be received.
+    InProcess {
+        solver: Box<dyn InProcessSolver>,
+        response: Option<ChildToParent<'static>>,
+    },
+}
+
+/// [`ChildState`] with the solver type erased.
+trait InProcessSolver {
+    fn handle(&mut self, received: ParentToChild<'static>) -> Option<ChildToParent<'static>>;
+}
+
+impl<S: ChildSolver + ?Sized> InProcessSolver for ChildState<S> {
+    fn handle(&mut self, received: ParentToChild<'static>) -> Option<ChildToParent<'static>> {
+        ChildState::handle(self, received).map(ChildToParent::into_owned)
+    }
 }
 
 impl ParentSolver {
-    /// Start up the manager of a child solver.
+    /// Start up the manager of the child solver in cargo package `package`.
     ///
-    /// Compiles, runs, and sends input to the child.
-    pub fn new(day: u32, input: &[u8], debug: u8, release: bool) -> Result<Self, SolverError> {
-        let mut process = Command::new("cargo");
-        process
-            .args(["run", "--package"])
-            .arg(format!("day{day:02}"))
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped());
+    /// Compiles, runs, and sends input to the child. Compiling doesn't count
+    /// toward the timeout.
+    ///
+    /// With `alloc_stats`, the child is built with a counting allocator and
+    /// reports heap usage for each part.
+    pub fn new(
+        package: &str,
+        input: &[u8],
+        debug: u8,
+        release: bool,
+        limits: Limits,
+        alloc_stats: bool,
+    ) -> Result<Self, SolverError> {
+        let mut command = Command::new("cargo");
+        command
+            .args(["run", "--package", package])
+            .args(["--profile", profile(release)]);
 
-        if release {
-            process.args(["--profile", "day-release"]);
-        } else {
-            process.args(["--profile", "day-dev"]);
+        if alloc_stats {
+            command.args(["--features", ALLOC_STATS_FEATURE]);
         }
 
-        let mut process = process.spawn()?;
+        Self::spawn(command, input, debug, limits)
+    }
+
+    /// Start up the manager of a child solver that's already been built,
+    /// without going through cargo.
+    pub fn from_binary(
+        binary: &Path,
+        input: &[u8],
+        debug: u8,
+        limits: Limits,
+    ) -> Result<Self, SolverError> {
+        Self::spawn(Command::new(binary), input, debug, limits)
+    }
+
+    fn spawn(
+        mut command: Command,
+        input: &[u8],
+        debug: u8,
+        limits: Limits,
+    ) -> Result<Self, SolverError> {
+        // With `cargo run`, the solver is cargo's child, so killing the
+        // process we started would leave it running. Giving them their own
+        // process group lets the whole group be killed instead. Ctrl-C no
+        // longer reaches them, but they quit once our end of stdin closes.
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+        let mut process = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
         let stdin = BufWriter::new(process.stdin.take().unwrap());
-        let stdout = BufReader::new(process.stdout.take().unwrap());
         let config = bincode_config();
+        let messages = spawn_reader(process.stdout.take().unwrap(), config);
 
         let mut this = Self {
-            process,
-            stdin,
-            stdout,
-            config,
+            backend: Backend::Child {
+                process,
+                stdin,
+                messages,
+                config,
+                dead: false,
+            },
+            limits,
         };
 
         let init = Initialization {
             input: input.into(),
             debug,
+            memory_limit: limits.memory,
         };
         this.initialize(init)?;
 
         Ok(this)
     }
 
-    /// Send new input to the child, overwriting the previous input.
+    /// Runs the solver `S` in this process instead of a child.
+    ///
+    /// Nothing can be stopped from outside, so there are no limits, and a
+    /// solver that aborts takes the whole process with it. Panics are still
+    /// caught.
+    pub fn in_process<S>(input: &[u8], debug: u8) -> Self
+    where
+        S: ChildSolver + ?Sized + 'static,
+        S::Parsed: 'static,
+    {
+        let init = Initialization {
+            input: input.to_vec().into(),
+            debug,
+            memory_limit: None,
+        };
+        Self {
+            backend: Backend::InProcess {
+                solver: Box::new(ChildState::<S>::new(init)),
+                response: None,
+            },
+            limits: Limits::default(),
+        }
+    }
+
+    /// Send new input to the child, overwriting the previous input, and wait
+    /// for the child to be ready.
     pub fn initialize(&mut self, init: Initialization) -> Result<(), SolverError> {
-        self.send(ParentToChild::Initialize(init))
+        let init = Initialization {
+            input: init.input.into_owned().into(),
+            ..init
+        };
+        self.send(ParentToChild::Initialize(init))?;
+        match self.receive(None)? {
+            ChildToParent::Initialized => Ok(()),
+            msg => Err(SolverError::ParentExpectedAnswer { received: msg }),
+        }
     }
 
-    fn send(&mut self, message: ParentToChild) -> Result<(), SolverError> {
-        encode_into_std_write(message, &mut self.stdin, self.config)?;
-        self.stdin.flush()?;
+    fn send(&mut self, message: ParentToChild<'static>) -> Result<(), SolverError> {
+        match &mut self.backend {
+            Backend::Child { stdin, config, .. } => {
+                encode_into_std_write(message, &mut *stdin, *config)?;
+                stdin.flush()?;
+            }
+            Backend::InProcess { solver, response } => *response = solver.handle(message),
+        }
         Ok(())
     }
 
-    fn receive(&mut self) -> Result<ChildToParent<'static>, SolverError> {
-        let msg = decode_from_reader(&mut self.stdout, self.config)?;
-        Ok(msg)
+    /// Waits for the next message, killing the child if it takes longer than
+    /// `timeout`. Errors and panics reported by the child are returned as
+    /// errors, and the child can still be used afterward.
+    fn receive(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> Result<ChildToParent<'static>, SolverError> {
+        let msg = match &mut self.backend {
+            Backend::Child { messages, .. } => match timeout {
+                Some(timeout) => match messages.recv_timeout(timeout) {
+                    Ok(msg) => msg,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.kill();
+                        return Err(SolverError::Timeout(timeout));
+                    }
+                    Err(RecvTimeoutError::Disconnected) => return Err(self.child_quit()),
+                },
+                None => match messages.recv() {
+                    Ok(msg) => msg,
+                    Err(_) => return Err(self.child_quit()),
+                },
+            },
+            Backend::InProcess { response, .. } => match response.take() {
+                Some(msg) => Ok(msg),
+                None => return Err(SolverError::ChildQuit),
+            },
+        };
+
+        match msg {
+            Ok(ChildToParent::Err(err)) => Err(SolverError::ChildError(err)),
+            Ok(ChildToParent::Panicked(panic)) => Err(SolverError::Panicked(panic)),
+            Ok(msg) => Ok(msg),
+            Err(DecodeError::UnexpectedEnd { .. }) => Err(self.child_quit()),
+            Err(DecodeError::Io { inner, .. })
+                if inner.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                Err(self.child_quit())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Finds out why the child stopped sending messages.
+    fn child_quit(&mut self) -> SolverError {
+        let Backend::Child { process, dead, .. } = &mut self.backend else {
+            return SolverError::ChildQuit;
+        };
+        *dead = true;
+        match process.wait() {
+            Ok(status) if status.code() == Some(OUT_OF_MEMORY_EXIT_CODE) => {
+                match self.limits.memory {
+                    Some(limit) => SolverError::MemoryLimit(limit),
+                    None => SolverError::ChildQuit,
+                }
+            }
+            Ok(_) => SolverError::ChildQuit,
+            Err(e) => e.into(),
+        }
+    }
+
+    fn kill(&mut self) {
+        let Backend::Child { process, dead, .. } = &mut self.backend else {
+            return;
+        };
+        *dead = true;
+        // The child may have exited on its own just now
+        kill_group(process);
+        let _ = process.wait();
+    }
+
+    /// Change the timeout for everything sent to the solver from now on.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.limits.timeout = timeout;
+    }
+
+    /// Parse the input ahead of running any parts. Returns `None` if the solver
+    /// doesn't parse separately from its parts.
+    pub fn parse(&mut self) -> Result<Option<Duration>, SolverError> {
+        self.send(ParentToChild::Parse)?;
+        match self.receive(self.limits.timeout)? {
+            ChildToParent::Parsed(time) => Ok(time),
+            msg => Err(SolverError::ParentExpectedAnswer { received: msg }),
+        }
     }
 
     /// Run part one.
-    pub fn part_one(&mut self, buffer: &mut String) -> Result<Duration, SolverError> {
+    pub fn part_one(&mut self, buffer: &mut String) -> Result<PartStats, SolverError> {
         self.run_any(1, buffer)
     }
 
     /// Run part two.
-    pub fn part_two(&mut self, buffer: &mut String) -> Result<Duration, SolverError> {
+    pub fn part_two(&mut self, buffer: &mut String) -> Result<PartStats, SolverError> {
         self.run_any(2, buffer)
     }
 
     /// Run any part besides part one or two.
-    pub fn run_any(&mut self, part: u32, buffer: &mut String) -> Result<Duration, SolverError> {
+    pub fn run_any(&mut self, part: u32, buffer: &mut String) -> Result<PartStats, SolverError> {
         self.send(ParentToChild::Run(Run { part }))?;
-        let ans = match self.receive()? {
+        let ans = match self.receive(self.limits.timeout)? {
             ChildToParent::Answer(ans) => ans,
-            ChildToParent::Err(err) => return Err(SolverError::ChildError(err)),
             msg => return Err(SolverError::ParentExpectedAnswer { received: msg }),
         };
         buffer.clear();
         buffer.push_str(&ans.answer);
-        Ok(ans.time)
+        Ok(PartStats {
+            time: ans.time,
+            alloc: ans.alloc,
+        })
     }
 
-    /// Run a benchmark.
-    pub fn bench(&mut self, part: u32, iters: u32) -> Result<BenchResult<'static>, SolverError> {
+    /// Run a benchmark, after running `warmup` untimed iterations.
+    pub fn bench(
+        &mut self,
+        part: u32,
+        iters: u32,
+        warmup: u32,
+    ) -> Result<BenchResult<'static>, SolverError> {
         self.send(ParentToChild::Bench(Bench {
             run: Run { part },
             iters,
+            warmup,
         }))?;
+        self.receive_bench(iters, warmup)
+    }
+
+    /// Benchmark parsing, after running `warmup` untimed iterations.
+    pub fn bench_parse(
+        &mut self,
+        iters: u32,
+        warmup: u32,
+    ) -> Result<BenchResult<'static>, SolverError> {
+        self.send(ParentToChild::BenchParse(BenchParse { iters, warmup }))?;
+        self.receive_bench(iters, warmup)
+    }
 
-        match self.receive()? {
+    fn receive_bench(
+        &mut self,
+        iters: u32,
+        warmup: u32,
+    ) -> Result<BenchResult<'static>, SolverError> {
+        // Every iteration gets the full timeout
+        let timeout = self
+            .limits
+            .timeout
+            .map(|t| t.saturating_mul(iters.saturating_add(warmup.max(1))));
+
+        match self.receive(timeout)? {
             ChildToParent::BenchResult(br) => {
                 let benches = br.times.len() as u32;
                 if benches != iters {
@@ -108,7 +361,6 @@ impl ParentSolver {
                 }
                 Ok(br)
             }
-            ChildToParent::Err(e) => Err(SolverError::ChildError(e)),
             msg => Err(SolverError::ParentExpectedAnswer { received: msg }),
         }
     }
@@ -116,7 +368,49 @@ impl ParentSolver {
 
 impl Drop for ParentSolver {
     fn drop(&mut self) {
-        self.send(ParentToChild::End).unwrap();
-        self.process.wait().unwrap();
+        let Backend::Child { dead, .. } = self.backend else {
+            return;
+        };
+        if !dead {
+            // The child can't be told to stop if it already crashed
+            let _ = self.send(ParentToChild::End);
+        }
+        if let Backend::Child { process, .. } = &mut self.backend {
+            let _ = process.wait();
+        }
     }
 }
+
+/// Kills the child along with anything it started, like the solver under
+/// `cargo run`.
+#[cfg(unix)]
+fn kill_group(process: &mut Child) {
+    let Ok(pid) = libc::pid_t::try_from(process.id()) else {
+        let _ = process.kill();
+        return;
+    };
+    // SAFETY: Only sends a signal to the group the child leads.
+    unsafe { libc::kill(-pid, libc::SIGKILL) };
+}
+
+#[cfg(not(unix))]
+fn kill_group(process: &mut Child) {
+    let _ = process.kill();
+}
+
+/// Decodes messages from the child on another thread, so the parent can stop
+/// waiting for them.
+fn spawn_reader(stdout: ChildStdout, config: Configuration) -> Receiver<Message> {
+    let (send, recv) = channel();
+    std::thread::spawn(move || {
+        let mut stdout = BufReader::new(stdout);
+        loop {
+            let msg: Message = decode_from_reader(&mut stdout, config);
+            let failed = msg.is_err();
+            if send.send(msg).is_err() || failed {
+                break;
+            }
+        }
+    });
+    recv
+}
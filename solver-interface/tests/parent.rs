@@ -0,0 +1,101 @@
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use solver_interface::{Limits, ParentSolver, SolverError};
+
+const SOLVER: &str = env!("CARGO_BIN_EXE_test-solver");
+
+fn start(binary: &Path, limits: Limits) -> ParentSolver {
+    ParentSolver::from_binary(binary, b"input", 0, limits).unwrap()
+}
+
+#[test]
+fn timeout_t() {
+    let timeout = Duration::from_millis(200);
+    let limits = Limits {
+        timeout: Some(timeout),
+        memory: None,
+    };
+    let mut solver = start(SOLVER.as_ref(), limits);
+
+    let start = Instant::now();
+    let result = solver.part_one(&mut String::new());
+    assert!(matches!(result, Err(SolverError::Timeout(t)) if t == timeout));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    // Dropping waits for the killed child
+    drop(solver);
+}
+
+/// Like `cargo run`, the script runs the solver as its own child, and the
+/// solver has to be killed along with it.
+#[test]
+#[cfg(target_os = "linux")]
+fn timeout_kills_grandchild_t() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir().join(format!("solver-interface-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let pid_file = dir.join("pid");
+    let script = dir.join("wrapper.sh");
+    std::fs::write(
+        &script,
+        format!(
+            "#!/bin/sh\nexec 3<&0\n'{SOLVER}' <&3 &\necho $! > '{}'\nwait\n",
+            pid_file.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(200)),
+        memory: None,
+    };
+    let mut solver = start(&script, limits);
+    let result = solver.part_one(&mut String::new());
+    assert!(matches!(result, Err(SolverError::Timeout(_))));
+    drop(solver);
+
+    let pid = std::fs::read_to_string(&pid_file).unwrap();
+    let stat = Path::new("/proc").join(pid.trim()).join("stat");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    // A killed process may linger as a zombie until something reaps it
+    while let Ok(stat) = std::fs::read_to_string(&stat) {
+        let state = stat.rsplit(") ").next().unwrap();
+        if state.starts_with('Z') {
+            break;
+        }
+        assert!(Instant::now() < deadline, "solver is still running");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn panic_t() {
+    let mut solver = start(SOLVER.as_ref(), Limits::default());
+    let Err(SolverError::Panicked(panic)) = solver.part_two(&mut String::new()) else {
+        panic!("part 2 should panic");
+    };
+    assert_eq!(&*panic.message, "bad input \"input\"");
+
+    // The child keeps going after a panic
+    let result = solver.part_two(&mut String::new());
+    assert!(matches!(result, Err(SolverError::Panicked(_))));
+}
+
+#[test]
+#[cfg(unix)]
+fn memory_limit_t() {
+    let memory = 256 * 1024 * 1024;
+    let limits = Limits {
+        timeout: None,
+        memory: Some(memory),
+    };
+    let mut solver = start(SOLVER.as_ref(), limits);
+    let result = solver.run_any(3, &mut String::new());
+    assert!(
+        matches!(result, Err(SolverError::MemoryLimit(m)) if m == memory),
+        "{result:?}"
+    );
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::Deserialize;
+use solver_interface::{ALLOC_STATS_FEATURE, profile};
+
+use crate::{AocError, Res};
+
+/// The result of building every requested day with one `cargo build`.
+#[derive(Debug, Default)]
+pub struct Build {
+    /// Solver executables by day.
+    pub binaries: BTreeMap<u32, PathBuf>,
+    /// Rendered compiler errors by crate. Days that failed show up here
+    /// instead of in `binaries`.
+    pub errors: BTreeMap<String, Vec<String>>,
+    /// Number of compiler warnings by crate.
+    pub warnings: BTreeMap<String, usize>,
+}
+
+impl Build {
+    /// Adds the results of another build.
+    pub fn extend(&mut self, other: Build) {
+        self.binaries.extend(other.binaries);
+        self.errors.extend(other.errors);
+        for (krate, warnings) in other.warnings {
+            *self.warnings.entry(krate).or_default() += warnings;
+        }
+    }
+}
+
+/// One line of `cargo build --message-format json`. Only the fields the
+/// runner needs are read.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerArtifact {
+        target: Target,
+        executable: Option<PathBuf>,
+    },
+    CompilerMessage {
+        target: Target,
+        message: Diagnostic,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    level: String,
+    rendered: Option<String>,
+}
+
+/// Builds the days with the same profile and features that `cargo run` would
+/// use for them. Compiler errors are collected instead of printed.
+pub fn build_days(
+    packages: impl IntoIterator<Item = String>,
+    release: bool,
+    alloc_stats: bool,
+) -> Res<Build> {
+    let mut command = Command::new("cargo");
+    command
+        .args(["build", "--keep-going", "--message-format", "json"])
+        .args(["--profile", profile(release)]);
+    for package in packages {
+        command.args(["--package", &package]);
+    }
+    if alloc_stats {
+        command.args(["--features", ALLOC_STATS_FEATURE]);
+    }
+
+    let output = command.output()?;
+    let build = parse_messages(&output.stdout)?;
+
+    // Cargo itself failed, like when a manifest is broken
+    if !output.status.success() && build.errors.is_empty() {
+        return Err(AocError::cargo_build(String::from_utf8_lossy(
+            &output.stderr,
+        )));
+    }
+
+    Ok(build)
+}
+
+/// Collects binaries and diagnostics from cargo's JSON messages, one per line.
+fn parse_messages(stdout: &[u8]) -> Res<Build> {
+    let mut build = Build::default();
+    for line in stdout.split(|&b| b == b'\n') {
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_slice(line)? {
+            CargoMessage::CompilerArtifact {
+                target,
+                executable: Some(executable),
+            } => {
+                if let Some(day) = target_day(&target.name) {
+                    build.binaries.insert(day, executable);
+                }
+            }
+            CargoMessage::CompilerMessage { target, message } => match &*message.level {
+                "error" => build
+                    .errors
+                    .entry(target.name)
+                    .or_default()
+                    .extend(message.rendered),
+                "warning" => *build.warnings.entry(target.name).or_default() += 1,
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+    Ok(build)
+}
+
+/// The day of a solver's crate name, like `day05` or `y2024-day05`.
+fn target_day(name: &str) -> Option<u32> {
+    let day = match name.split_once("-day") {
+        Some((year, day)) if year.starts_with('y') => day,
+        _ => name.strip_prefix("day")?,
+    };
+    day.parse().ok()
+}
+
+#[test]
+fn target_day_t() {
+    assert_eq!(target_day("day05"), Some(5));
+    assert_eq!(target_day("day12"), Some(12));
+    assert_eq!(target_day("y2024-day05"), Some(5));
+    assert_eq!(target_day("y2015-day25"), Some(25));
+    assert_eq!(target_day("helpers"), None);
+    assert_eq!(target_day("test-solver"), None);
+    assert_eq!(target_day("x2024-day05"), None);
+    assert_eq!(target_day("dayfive"), None);
+}
+
+#[test]
+fn parse_messages_t() {
+    // Trimmed from `cargo build --keep-going --message-format json` where
+    // day05 fails to compile and the others still build
+    let stdout = br#"{"reason":"compiler-artifact","package_id":"path+file:///aoc/days/day01#0.1.0","target":{"kind":["lib"],"crate_types":["lib"],"name":"day01","src_path":"/aoc/days/day01/src/lib.rs"},"filenames":["/aoc/target/day-dev/libday01.rlib"],"executable":null,"fresh":true}
+{"reason":"compiler-message","package_id":"path+file:///aoc/days/day01#0.1.0","target":{"kind":["lib"],"crate_types":["lib"],"name":"day01","src_path":"/aoc/days/day01/src/lib.rs"},"message":{"rendered":"warning: unused variable: `x`\n","$message_type":"diagnostic","children":[],"code":{"code":"unused_variables","explanation":null},"level":"warning","message":"unused variable: `x`","spans":[]}}
+{"reason":"compiler-artifact","package_id":"path+file:///aoc/days/day01#0.1.0","target":{"kind":["bin"],"crate_types":["bin"],"name":"day01","src_path":"/aoc/days/day01/src/main.rs"},"filenames":["/aoc/target/day-dev/day01"],"executable":"/aoc/target/day-dev/day01","fresh":false}
+{"reason":"compiler-message","package_id":"path+file:///aoc/days/day05#0.1.0","target":{"kind":["lib"],"crate_types":["lib"],"name":"day05","src_path":"/aoc/days/day05/src/lib.rs"},"message":{"rendered":"error[E0425]: cannot find value `y` in this scope\n","$message_type":"diagnostic","children":[],"code":{"code":"E0425","explanation":null},"level":"error","message":"cannot find value `y` in this scope","spans":[]}}
+{"reason":"compiler-message","package_id":"path+file:///aoc/days/day05#0.1.0","target":{"kind":["lib"],"crate_types":["lib"],"name":"day05","src_path":"/aoc/days/day05/src/lib.rs"},"message":{"rendered":"error: aborting due to 1 previous error\n\n","$message_type":"diagnostic","children":[],"code":null,"level":"error","message":"aborting due to 1 previous error","spans":[]}}
+{"reason":"compiler-artifact","package_id":"path+file:///aoc/days/y2024-day03#0.1.0","target":{"kind":["bin"],"crate_types":["bin"],"name":"y2024-day03","src_path":"/aoc/days/y2024-day03/src/main.rs"},"filenames":["/aoc/target/day-dev/y2024-day03"],"executable":"/aoc/target/day-dev/y2024-day03","fresh":true}
+{"reason":"build-finished","success":false}
+"#;
+    let build = parse_messages(stdout).unwrap();
+    assert_eq!(
+        build.binaries,
+        BTreeMap::from([
+            (1, PathBuf::from("/aoc/target/day-dev/day01")),
+            (3, PathBuf::from("/aoc/target/day-dev/y2024-day03")),
+        ])
+    );
+    assert_eq!(build.errors.len(), 1);
+    assert_eq!(build.errors["day05"].len(), 2);
+    assert!(build.errors["day05"][0].starts_with("error[E0425]"));
+    assert_eq!(build.warnings, BTreeMap::from([("day01".to_string(), 1)]));
+
+    assert!(parse_messages(b"not json\n").is_err());
+}
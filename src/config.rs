@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, ArgAction, Command};
+use serde::Deserialize;
+use toml::{Table, Value};
+
+use crate::{AocError, Res};
+
+/// Name of the config file, both in the project and the user config directory.
+const CONFIG_FILE: &str = "aoc.toml";
+
+/// The runner's directory in the user config directory, which is
+/// `$XDG_CONFIG_HOME`, or `~/.config` when that isn't set.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config.join("aoc"))
+}
+
+/// Defaults from the user's `aoc.toml` and then the project's, so the project
+/// wins.
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Command line flags for the top-level settings by argument id, to be
+    /// parsed before the real command line.
+    pub args: BTreeMap<String, Vec<String>>,
+    /// Day tables by year and day. Tables without a year, like `[days.10]`,
+    /// have `None` and are for the year given with `--year`.
+    pub days: BTreeMap<(Option<u32>, u32), DayConfig>,
+}
+
+/// Settings that can be changed for a single day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DayConfig {
+    pub release: Option<bool>,
+    pub debug: Option<u8>,
+    /// Milliseconds, like `--timeout`.
+    pub timeout: Option<u64>,
+    /// Mebibytes, like `--memory-limit`.
+    pub memory_limit: Option<u64>,
+    #[serde(default)]
+    pub parts: BTreeMap<String, PartConfig>,
+}
+
+/// Settings that can be changed for a single part.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PartConfig {
+    /// Milliseconds, like `--timeout`.
+    pub timeout: Option<u64>,
+}
+
+impl DayConfig {
+    /// Fills in anything this doesn't set from `base`.
+    fn or(mut self, base: DayConfig) -> DayConfig {
+        for (part, config) in base.parts {
+            let entry = self.parts.entry(part).or_default();
+            entry.timeout = entry.timeout.or(config.timeout);
+        }
+        DayConfig {
+            release: self.release.or(base.release),
+            debug: self.debug.or(base.debug),
+            timeout: self.timeout.or(base.timeout),
+            memory_limit: self.memory_limit.or(base.memory_limit),
+            parts: self.parts,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    days: BTreeMap<String, DayConfig>,
+    #[serde(flatten)]
+    settings: Table,
+}
+
+impl Config {
+    /// Reads the config files that exist. `command` is used to check the
+    /// settings and turn them into flags.
+    pub fn load(command: &Command) -> Res<Self> {
+        let user = user_config_dir().map(|dir| dir.join(CONFIG_FILE));
+        let project = Some(PathBuf::from(CONFIG_FILE));
+
+        let mut config = Config::default();
+        for path in [user, project].into_iter().flatten() {
+            let text = match std::fs::read_to_string(&path) {
+                Ok(text) => text,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            config.add(&path, &text, command)?;
+        }
+        Ok(config)
+    }
+
+    fn add(&mut self, path: &Path, text: &str, command: &Command) -> Res<()> {
+        let error = |message: String| AocError::config(path, message);
+        let file: ConfigFile = toml::from_str(text).map_err(|e| error(e.to_string()))?;
+
+        for (key, value) in &file.settings {
+            let (id, args) = setting_args(command, key, value).map_err(error)?;
+            self.args.insert(id, args);
+        }
+
+        for (key, config) in file.days {
+            let day = parse_day_key(&key).ok_or_else(|| {
+                error(format!(
+                    "`days.{key}` should be a day number, or a year and day like \"2024/10\""
+                ))
+            })?;
+            if let Some(part) = config.parts.keys().find(|p| p.parse::<u32>().is_err()) {
+                return Err(error(format!(
+                    "`days.{key}.parts.{part}` should be a part number"
+                )));
+            }
+            let base = self.days.remove(&day).unwrap_or_default();
+            self.days.insert(day, config.or(base));
+        }
+        Ok(())
+    }
+
+    /// The day tables by year and day, with tables without a year put in
+    /// `year`. A table that names the year wins over one that doesn't.
+    pub fn days_in(&self, year: u32) -> BTreeMap<(u32, u32), DayConfig> {
+        let mut days = BTreeMap::new();
+        for (&(table_year, day), config) in &self.days {
+            if let Some(table_year) = table_year {
+                days.insert((table_year, day), config.clone());
+            }
+        }
+        for (&(table_year, day), config) in &self.days {
+            if table_year.is_none() {
+                let base = config.clone();
+                let entry: &mut DayConfig = days.entry((year, day)).or_default();
+                *entry = std::mem::take(entry).or(base);
+            }
+        }
+        days
+    }
+}
+
+/// Reads a day table's key, which is a day or `year/day`.
+fn parse_day_key(key: &str) -> Option<(Option<u32>, u32)> {
+    match key.split_once('/') {
+        Some((year, day)) => Some((Some(year.parse().ok()?), day.parse().ok()?)),
+        None => Some((None, key.parse().ok()?)),
+    }
+}
+
+/// Turns a setting into the id of its argument and the flags that would set it
+/// on the command line. Keys are the long flag names, with `-` or `_` between
+/// words.
+fn setting_args(
+    command: &Command,
+    key: &str,
+    value: &Value,
+) -> Result<(String, Vec<String>), String> {
+    let long = key.replace('_', "-");
+    let Some(arg) = command
+        .get_arguments()
+        .find(|arg| arg.get_long() == Some(&long))
+    else {
+        return Err(format!("unknown setting `{key}`"));
+    };
+    let flag = format!("--{long}");
+
+    let wrong_type = || format!("`{key}` can't be set to {value}");
+    let args = match (arg.get_action(), value) {
+        (ArgAction::SetTrue, &Value::Boolean(set)) => {
+            if set {
+                vec![flag]
+            } else {
+                Vec::new()
+            }
+        }
+        (ArgAction::Count, &Value::Integer(count)) => {
+            let count = usize::try_from(count).map_err(|_| wrong_type())?;
+            vec![flag; count]
+        }
+        (ArgAction::Set, value) => {
+            vec![format!("{flag}={}", scalar(value).ok_or_else(wrong_type)?)]
+        }
+        (ArgAction::Append, Value::Array(values)) => values
+            .iter()
+            .map(|value| Some(format!("{flag}={}", scalar(value)?)))
+            .collect::<Option<_>>()
+            .ok_or_else(wrong_type)?,
+        _ => return Err(wrong_type()),
+    };
+    Ok((arg.get_id().to_string(), args))
+}
+
+/// Adds a hidden `--no-<flag>` for every on/off and counted flag, so one that
+/// `aoc.toml` turns on can be turned off again. Whichever comes last wins, and
+/// the config's flags always come first.
+pub fn with_negations(mut command: Command) -> Command {
+    let flags: Vec<(String, String)> = command
+        .get_arguments()
+        .filter(|arg| matches!(arg.get_action(), ArgAction::SetTrue | ArgAction::Count))
+        .filter_map(|arg| Some((arg.get_id().to_string(), arg.get_long()?.to_string())))
+        .collect();
+    for (id, long) in flags {
+        let negation = format!("no_{id}");
+        command = command
+            .mut_arg(&id, |arg| arg.overrides_with(negation.clone()))
+            .arg(
+                Arg::new(negation)
+                    .long(format!("no-{long}"))
+                    .action(ArgAction::SetTrue)
+                    .overrides_with(id)
+                    .hide(true),
+            );
+    }
+    command
+}
+
+/// A value as it would be written on the command line.
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Integer(i) => Some(i.to_string()),
+        Value::Float(f) => Some(f.to_string()),
+        Value::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[test]
+fn config_t() {
+    use clap::CommandFactory;
+
+    let command = crate::Settings::command();
+    let mut config = Config::default();
+    let user = r#"
+        release = true
+        debug = 2
+        bench-time = 500
+
+        [days.9]
+        timeout = 1000
+        parts.2.timeout = 5000
+    "#;
+    let project = r#"
+        hide_answers = true
+        mode = "bench"
+        debug = 1
+
+        [days.9]
+        timeout = 2000
+
+        [days.10]
+        release = false
+    "#;
+    config.add(Path::new("user"), user, &command).unwrap();
+    config.add(Path::new("project"), project, &command).unwrap();
+
+    let args: Vec<&str> = config.args.values().flatten().map(|a| &**a).collect();
+    assert_eq!(
+        args,
+        [
+            "--bench-time=500",
+            "--debug",
+            "--hide-answers",
+            "--mode=bench",
+            "--release"
+        ]
+    );
+    assert_eq!(config.days[&(None, 9)].timeout, Some(2000));
+    assert_eq!(config.days[&(None, 9)].parts["2"].timeout, Some(5000));
+    assert_eq!(config.days[&(None, 10)].release, Some(false));
+
+    // Tables without a year are only for the default year
+    let text = "[days.10]\nrelease = true\ndebug = 1\n\n[days.\"2024/10\"]\nrelease = false\n";
+    let mut config = Config::default();
+    config.add(Path::new("x"), text, &command).unwrap();
+    let days = config.days_in(2024);
+    assert_eq!(days[&(2024, 10)].release, Some(false));
+    assert_eq!(days[&(2024, 10)].debug, Some(1));
+    assert_eq!(config.days_in(2025)[&(2024, 10)].debug, None);
+    assert_eq!(config.days_in(2025)[&(2025, 10)].release, Some(true));
+
+    // The command line can turn off what the config turned on
+    use clap::FromArgMatches;
+    let command = with_negations(command);
+    let parse = |args: &[&str]| {
+        let matches = command.clone().try_get_matches_from(args).unwrap();
+        crate::Settings::from_arg_matches(&matches).unwrap()
+    };
+    let settings = parse(&[
+        "aoc",
+        "--release",
+        "--debug",
+        "--debug",
+        "--no-release",
+        "--no-debug",
+    ]);
+    assert!(!settings.release);
+    assert_eq!(settings.debug, 0);
+    let settings = parse(&["aoc", "--no-hide-answers", "--hide-answers"]);
+    assert!(settings.hide_answers);
+
+    let mut config = Config::default();
+    assert!(config.add(Path::new("x"), "colour = 1", &command).is_err());
+    assert!(config.add(Path::new("x"), "release = 1", &command).is_err());
+    assert!(config.add(Path::new("x"), "[days.x]", &command).is_err());
+}
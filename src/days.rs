@@ -0,0 +1,26 @@
+use solver_interface::ParentSolver;
+
+use crate::{AocError, Res};
+
+/// Starts a day's solver in this process. Every day linked into the runner for
+/// `--in-process` is listed here.
+pub(crate) fn start(day: u32, input: &[u8], debug: u8) -> Res<ParentSolver> {
+    Ok(match day {
+        1 => ParentSolver::in_process::<day01::Solver>(input, debug),
+        2 => ParentSolver::in_process::<day02::Solver>(input, debug),
+        3 => ParentSolver::in_process::<day03::Solver>(input, debug),
+        4 => ParentSolver::in_process::<day04::Solver>(input, debug),
+        5 => ParentSolver::in_process::<day05::Solver>(input, debug),
+        6 => ParentSolver::in_process::<day06::Solver>(input, debug),
+        7 => ParentSolver::in_process::<day07::Solver>(input, debug),
+        8 => ParentSolver::in_process::<day08::Solver>(input, debug),
+        9 => ParentSolver::in_process::<day09::Solver>(input, debug),
+        #[cfg(feature = "in-process-day10")]
+        10 => ParentSolver::in_process::<day10::SolverAoc>(input, debug),
+        #[cfg(not(feature = "in-process-day10"))]
+        10 => return Err(AocError::InProcessUnavailable("in-process-day10")),
+        11 => ParentSolver::in_process::<day11::Solver>(input, debug),
+        12 => ParentSolver::in_process::<day12::Solver>(input, debug),
+        _ => return Err(AocError::DayNotFound(day)),
+    })
+}
@@ -12,12 +12,35 @@ pub enum AocError {
     PartNotFound,
     #[error(transparent)]
     HasNotReleasedYet(HasNotReleasedYet),
+    #[error("couldn't submit answer. Status {status}, content:\n{response}")]
+    SubmitResponse {
+        status: StatusCode,
+        response: Box<str>,
+    },
+    #[error("refusing to submit: {reason}")]
+    SubmitRefused { reason: Box<str> },
+    #[error("answer was not accepted: {0}")]
+    NotAccepted(Box<str>),
+    #[error("only answers for the real input can be submitted")]
+    SubmitTestInput,
     #[error("no test input found with the name {path}")]
     NoTestInputFound { path: Box<str> },
-    #[error("the file API_KEY was not found")]
-    NoApiKey,
-    #[error("I/O problem while reading API_KEY file: {source}")]
-    ApiKeyIo { source: std::io::Error },
+    #[error("{path}: {message}")]
+    Config { path: Box<str>, message: Box<str> },
+    #[error("no session token found. Looked in {searched}")]
+    NoSession { searched: Box<str> },
+    #[error("I/O problem while reading session token from {path}: {source}")]
+    SessionIo {
+        path: Box<str>,
+        source: std::io::Error,
+    },
+    #[error("the session token from {from} is empty or malformed")]
+    InvalidSession { from: Box<str> },
+    #[error(
+        "the session token from {from} was not accepted. It has probably expired; log in \
+         again and copy the new session cookie"
+    )]
+    SessionExpired { from: Box<str> },
     #[error("couldn't fetch prompt from network. Status {status}, content:\n{response}")]
     PromptResponse {
         status: StatusCode,
@@ -28,12 +51,28 @@ pub enum AocError {
         status: StatusCode,
         response: Box<str>,
     },
+    #[error("unexpected response from {url}. Status {status}, content:\n{response}")]
+    SiteResponse {
+        url: Box<str>,
+        status: StatusCode,
+        response: Box<str>,
+    },
     #[error("no day specified in argument `{arg}`")]
     NoDaySpecified { arg: Box<str> },
     #[error(transparent)]
     Parse(Parse),
-    #[error("non-UTF-8 data found in code block on the prompt page")]
-    NonUtf8InPromptCodeBlock,
+    #[error("no leaderboard was given with `--leaderboard` or in aoc.toml")]
+    NoLeaderboard,
+    #[error("none of the selected days have the selected test inputs")]
+    NoTestInputs,
+    #[error(
+        "{path} has no `<!-- {marker}:start -->` and `<!-- {marker}:end -->` lines to write stats between"
+    )]
+    NoStatsBlock { path: Box<str>, marker: Box<str> },
+    #[error("can't fetch {url} with `--offline`")]
+    Offline { url: Box<str> },
+    #[error("the prompt page was not UTF-8")]
+    NonUtf8Prompt,
     #[error("non-UTF-8 data found in solution")]
     NonUtf8InSolution,
     #[error("day {0} not found")]
@@ -42,12 +81,42 @@ pub enum AocError {
     EmptyArgument,
     #[error("part was empty in {arg}")]
     EmptyPart { arg: Box<str> },
-    #[error("too many test cases were generated from the prompt")]
-    TooManyTestCases,
+    #[error("range has no days in {arg}")]
+    EmptyRange { arg: Box<str> },
+    #[error("only whole days can be left out, not parts like in {arg}")]
+    ExcludedParts { arg: Box<str> },
+    #[error("no puzzles from {0} have been released yet")]
+    NothingReleased(u32),
+    #[error("every puzzle from {0} has already been released")]
+    AllReleased(u32),
+    #[error("no puzzle from {0} releases today")]
+    NoPuzzleToday(u32),
     #[error("answers did not match, exiting run")]
     IncorrectAnswer,
     #[error("{0} answers were incorrect.")]
     MultipleIncorrect(u32),
+    #[error("{0} benchmarks were significantly slower than their baseline.")]
+    Regressions(u32),
+    #[error("{0} solvers panicked.")]
+    Panicked(u32),
+    #[error("--parallel can't be used {0}")]
+    ParallelUnsupported(&'static str),
+    #[error("the parallel worker for day {0} stopped without an answer")]
+    WorkerStopped(u32),
+    #[error("the runner was built without the `{0}` feature")]
+    InProcessUnavailable(&'static str),
+    #[error("days from {0} aren't linked into the runner, so they can't run in process")]
+    InProcessYear(u32),
+    #[error("{0} crates failed to build.")]
+    BuildFailed(usize),
+    #[error("cargo build failed:\n{stderr}")]
+    CargoBuild { stderr: Box<str> },
+    #[error("day {0} already exists")]
+    DayExists(u32),
+    #[error("no template directory found at {path}")]
+    NoTemplate { path: Box<str> },
+    #[error("no workspace members list found in Cargo.toml")]
+    NoWorkspaceMembers,
 
     #[error("request: {source}")]
     Request {
@@ -64,6 +133,11 @@ pub enum AocError {
         #[from]
         source: Box<notify::Error>,
     },
+    #[error("json: {source}")]
+    Json {
+        #[from]
+        source: serde_json::Error,
+    },
     #[error("fmt: {source}")]
     FmtError {
         #[from]
@@ -174,6 +248,11 @@ from_error_boxed! {
 }
 
 impl AocError {
+    /// Whether the solver panicked, which leaves the child running.
+    pub fn is_panic(&self) -> bool {
+        matches!(self, Self::Solver { source } if matches!(**source, SolverError::Panicked(_)))
+    }
+
     pub fn no_test_input_found(s: impl Into<Box<str>>) -> Self {
         Self::NoTestInputFound { path: s.into() }
     }
@@ -182,10 +261,69 @@ impl AocError {
         Self::Parse(Parse::new(part, arg))
     }
 
+    pub fn cargo_build(stderr: impl Into<Box<str>>) -> Self {
+        Self::CargoBuild {
+            stderr: stderr.into(),
+        }
+    }
+
+    pub fn submit_response(status: StatusCode, response: impl Into<Box<str>>) -> Self {
+        Self::SubmitResponse {
+            status,
+            response: response.into(),
+        }
+    }
+
+    pub fn submit_refused(reason: impl Into<Box<str>>) -> Self {
+        Self::SubmitRefused {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn no_template(path: impl Into<Box<str>>) -> Self {
+        Self::NoTemplate { path: path.into() }
+    }
+
     pub fn empty_part(arg: impl Into<Box<str>>) -> Self {
         Self::EmptyPart { arg: arg.into() }
     }
 
+    pub fn empty_range(arg: impl Into<Box<str>>) -> Self {
+        Self::EmptyRange { arg: arg.into() }
+    }
+
+    pub fn excluded_parts(arg: impl Into<Box<str>>) -> Self {
+        Self::ExcludedParts { arg: arg.into() }
+    }
+
+    pub fn config(path: &std::path::Path, message: impl Into<Box<str>>) -> Self {
+        Self::Config {
+            path: path.display().to_string().into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn no_session(searched: impl Into<Box<str>>) -> Self {
+        Self::NoSession {
+            searched: searched.into(),
+        }
+    }
+
+    pub fn session_io(path: &std::path::Path, source: std::io::Error) -> Self {
+        Self::SessionIo {
+            path: path.display().to_string().into(),
+            source,
+        }
+    }
+
+    pub fn invalid_session(from: impl Into<Box<str>>) -> Self {
+        Self::InvalidSession { from: from.into() }
+    }
+
+    pub fn session_expired(from: impl Into<Box<str>>) -> Self {
+        Self::SessionExpired { from: from.into() }
+    }
+
     pub fn prompt_response(status: StatusCode, response: impl Into<Box<str>>) -> Self {
         Self::PromptResponse {
             status,
@@ -193,6 +331,29 @@ impl AocError {
         }
     }
 
+    pub fn site_response(
+        url: impl Into<Box<str>>,
+        status: StatusCode,
+        response: impl Into<Box<str>>,
+    ) -> Self {
+        Self::SiteResponse {
+            url: url.into(),
+            status,
+            response: response.into(),
+        }
+    }
+
+    pub fn no_stats_block(path: impl Into<Box<str>>, marker: impl Into<Box<str>>) -> Self {
+        Self::NoStatsBlock {
+            path: path.into(),
+            marker: marker.into(),
+        }
+    }
+
+    pub fn offline(url: impl Into<Box<str>>) -> Self {
+        Self::Offline { url: url.into() }
+    }
+
     pub fn input_response(status: StatusCode, response: impl Into<Box<str>>) -> Self {
         Self::InputResponse {
             status,
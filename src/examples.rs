@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use crate::html::{Token, tokens};
+
+/// An example input from a puzzle prompt, saved as a test input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Example {
+    /// Number of the test, as in `inputNN.txt`.
+    pub test: u8,
+    /// The part whose description the example is in.
+    pub part: u32,
+    /// Answers given for the example, by part. Empty when the prompt didn't
+    /// have one.
+    pub answers: Vec<String>,
+    /// Kept in the input file, not the manifest.
+    #[serde(skip)]
+    pub input: String,
+}
+
+/// Something in a part's description that matters for finding examples.
+#[derive(Debug)]
+enum Item {
+    /// A `<pre>` block, and the text of the paragraph before it.
+    Block { code: String, before: String },
+    /// Emphasized code, like `<code><em>142</em></code>`, which is how answers
+    /// are shown.
+    Emphasized(String),
+}
+
+/// Finds the example inputs in a puzzle page along with their answers.
+///
+/// A code block is an example when the paragraph introducing it mentions an
+/// example. Other blocks, like the steps of working through one, are
+/// skipped. The answer is the last emphasized code before the next example or
+/// the end of the part. A part 2 without examples of its own is taken to be
+/// about the first example of part 1.
+pub fn find_examples(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (i, items) in parts(html).into_iter().enumerate() {
+        let part = i as u32 + 1;
+        let first_of_part = examples.len();
+        let mut answer = None;
+
+        for item in items {
+            match item {
+                Item::Block { code, before } => {
+                    if !before.to_lowercase().contains("example") {
+                        continue;
+                    }
+                    if let Some(answer) = answer.take() {
+                        set_answer(&mut examples[first_of_part..], part, answer);
+                    }
+                    let Ok(test) = u8::try_from(examples.len() + 1) else {
+                        break;
+                    };
+                    examples.push(Example {
+                        test,
+                        part,
+                        answers: Vec::new(),
+                        input: code,
+                    });
+                }
+                Item::Emphasized(code) => answer = Some(code),
+            }
+        }
+
+        if let Some(answer) = answer {
+            let examples = match &mut examples[..] {
+                [] => continue,
+                all if all.len() == first_of_part => &mut all[..1],
+                all => &mut all[first_of_part..],
+            };
+            set_answer(examples, part, answer);
+        }
+    }
+    examples
+}
+
+/// Every `<pre>` block in the puzzle's description, examples or not.
+pub fn code_blocks(html: &str) -> Vec<String> {
+    parts(html)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| match item {
+            Item::Block { code, .. } => Some(code),
+            Item::Emphasized(_) => None,
+        })
+        .collect()
+}
+
+/// Sets the answer to `part` on the last of `examples`.
+fn set_answer(examples: &mut [Example], part: u32, answer: String) {
+    let Some(example) = examples.last_mut() else {
+        return;
+    };
+    let index = part as usize - 1;
+    if example.answers.len() <= index {
+        example.answers.resize(index + 1, String::new());
+    }
+    example.answers[index] = answer;
+}
+
+/// The items in each `<article>`, which holds one part's description.
+fn parts(html: &str) -> Vec<Vec<Item>> {
+    let mut parts = Vec::new();
+    let mut items = Vec::new();
+    let mut in_article = false;
+    let mut pre = None;
+    let mut paragraph = String::new();
+    // Depth of `<code>` and `<em>` tags, and the text inside both
+    let (mut code, mut em) = (0u32, 0u32);
+    let mut emphasized = String::new();
+
+    for token in tokens(html) {
+        match token {
+            Token::Open { name, .. } if name == "article" => {
+                in_article = true;
+                items = Vec::new();
+            }
+            Token::Close(name) if name == "article" => {
+                in_article = false;
+                parts.push(std::mem::take(&mut items));
+            }
+            _ if !in_article => (),
+            Token::Open { name, .. } => match &*name {
+                "pre" => pre = Some(String::new()),
+                "p" | "li" => paragraph.clear(),
+                "code" => code += 1,
+                "em" => em += 1,
+                _ => (),
+            },
+            Token::Close(name) => {
+                let was_emphasized = code > 0 && em > 0;
+                match &*name {
+                    "pre" => {
+                        if let Some(code) = pre.take() {
+                            items.push(Item::Block {
+                                code,
+                                before: std::mem::take(&mut paragraph),
+                            });
+                        }
+                    }
+                    "code" => code = code.saturating_sub(1),
+                    "em" => em = em.saturating_sub(1),
+                    _ => (),
+                }
+                if was_emphasized && !(code > 0 && em > 0) && pre.is_none() {
+                    items.push(Item::Emphasized(std::mem::take(&mut emphasized)));
+                }
+            }
+            Token::Text(text) => match &mut pre {
+                Some(pre) => pre.push_str(&text),
+                None => {
+                    paragraph.push_str(&text);
+                    if code > 0 && em > 0 {
+                        emphasized.push_str(&text);
+                    }
+                }
+            },
+        }
+    }
+    parts
+}
+
+/// Answer file contents with `answers` filled in over `saved`, which keeps
+/// answers the prompt didn't give.
+pub fn merge_answers(saved: &str, answers: &[String]) -> String {
+    let mut lines: Vec<&str> = saved.lines().collect();
+    if lines.len() < answers.len() {
+        lines.resize(answers.len(), "");
+    }
+    for (line, answer) in lines.iter_mut().zip(answers) {
+        if !answer.is_empty() {
+            *line = answer;
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[test]
+fn find_examples_t() {
+    let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>Some text with <code>inline</code> code.</p>
+<p>For example:</p>
+<pre><code>1 2
+3 &gt; 4
+</code></pre>
+<p>After one step:</p>
+<pre><code>4 3
+</code></pre>
+<p>This gives <code><em>5</em></code> and then <em><code>10</code></em>.</p>
+<p>Here is a second example:</p>
+<pre><code>x
+</code></pre>
+<p>Which makes <code><em>7</em></code>.</p>
+<p>What is the <em>total</em>?</p>
+</article>
+<p>Your puzzle answer was <code>123</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Using the same example, the answer is now <code><em>42</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    let examples = find_examples(html);
+    assert_eq!(
+        examples,
+        [
+            Example {
+                test: 1,
+                part: 1,
+                answers: vec!["10".to_string(), "42".to_string()],
+                input: "1 2\n3 > 4\n".to_string(),
+            },
+            Example {
+                test: 2,
+                part: 1,
+                answers: vec!["7".to_string()],
+                input: "x\n".to_string(),
+            },
+        ]
+    );
+
+    assert_eq!(code_blocks(html)[1], "4 3\n");
+
+    assert_eq!(merge_answers("", &examples[1].answers), "7\n");
+    assert_eq!(
+        merge_answers("1\n2\n", &[String::new(), "3".to_string()]),
+        "1\n3\n"
+    );
+}
@@ -0,0 +1,166 @@
+use std::process::Command;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::stats::Stats;
+
+/// Two-sided critical value for Welch's t-test at roughly 99% confidence. Bench
+/// runs usually have enough samples that the normal approximation is fine.
+const SIGNIFICANT_T: f64 = 2.576;
+
+/// One benchmark run of a day and part, as stored in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub label: String,
+    pub commit: Option<String>,
+    pub timestamp: String,
+    pub test: u8,
+    pub samples: usize,
+    pub mean_ns: f64,
+    pub stddev_ns: f64,
+    pub median_ns: u64,
+}
+
+impl HistoryEntry {
+    /// Summarizes benchmark statistics into an entry labeled with `label`, or
+    /// `commit` if there's no label.
+    pub fn new(label: Option<&str>, commit: Option<String>, test: u8, stats: &Stats) -> Self {
+        let label = label
+            .map(str::to_string)
+            .or_else(|| commit.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            label,
+            commit,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            test,
+            samples: stats.samples,
+            mean_ns: stats.mean.as_nanos() as f64,
+            stddev_ns: stats.stddev.as_nanos() as f64,
+            median_ns: stats.median.as_nanos() as u64,
+        }
+    }
+
+    /// Compares this entry to an older one.
+    pub fn compare(&self, baseline: &HistoryEntry) -> Comparison {
+        let change = self.mean_ns / baseline.mean_ns - 1.0;
+
+        let variance = self.stddev_ns.powi(2) / self.samples as f64
+            + baseline.stddev_ns.powi(2) / baseline.samples as f64;
+        let significant = if variance > 0.0 {
+            ((self.mean_ns - baseline.mean_ns) / variance.sqrt()).abs() > SIGNIFICANT_T
+        } else {
+            self.mean_ns != baseline.mean_ns
+        };
+
+        Comparison {
+            baseline: baseline.label.clone(),
+            change,
+            significant,
+        }
+    }
+}
+
+/// The change in mean time between a benchmark and its baseline.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub baseline: String,
+    /// Relative change, where `0.1` means 10% slower.
+    pub change: f64,
+    /// Whether Welch's t-test says the difference isn't just noise.
+    pub significant: bool,
+}
+
+impl Comparison {
+    pub fn is_regression(&self) -> bool {
+        self.significant && self.change > 0.0
+    }
+
+    pub fn verdict(&self) -> &'static str {
+        match (self.significant, self.change > 0.0) {
+            (false, _) => "no significant change",
+            (true, true) => "regression",
+            (true, false) => "improvement",
+        }
+    }
+}
+
+/// Finds the entry to compare against: the latest one with the baseline label
+/// if there is one, otherwise the latest one. Only entries for the same test
+/// input are considered.
+pub fn find_baseline<'a>(
+    entries: &'a [HistoryEntry],
+    test: u8,
+    baseline: Option<&str>,
+) -> Option<&'a HistoryEntry> {
+    entries
+        .iter()
+        .rev()
+        .filter(|e| e.test == test)
+        .find(|e| baseline.is_none_or(|b| e.label == b))
+}
+
+/// The current commit from `git describe`, or `None` outside a git checkout.
+pub fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_string())
+}
+
+#[test]
+fn history_t() {
+    let entry = |label: &str, test, samples, mean_ns, stddev_ns| HistoryEntry {
+        label: label.to_string(),
+        commit: None,
+        timestamp: String::new(),
+        test,
+        samples,
+        mean_ns,
+        stddev_ns,
+        median_ns: mean_ns as u64,
+    };
+
+    let history = [
+        entry("a", 0, 100, 1000.0, 50.0),
+        entry("b", 0, 100, 1000.0, 50.0),
+        entry("a", 1, 100, 1000.0, 50.0),
+    ];
+    assert_eq!(find_baseline(&history, 0, None).unwrap().label, "b");
+    assert_eq!(find_baseline(&history, 0, Some("a")).unwrap().test, 0);
+    assert_eq!(
+        find_baseline(&history, 1, Some("b")).map(|e| &e.label),
+        None
+    );
+    assert!(find_baseline(&history, 2, None).is_none());
+
+    // A 2% change with these spreads is t = 20 / sqrt(50) ≈ 2.83
+    let baseline = &history[0];
+    let slower = entry("c", 0, 100, 1020.0, 50.0).compare(baseline);
+    assert_eq!(slower.baseline, "a");
+    assert!((slower.change - 0.02).abs() < 1e-9);
+    assert!(slower.is_regression());
+    assert_eq!(slower.verdict(), "regression");
+
+    let faster = entry("c", 0, 100, 980.0, 50.0).compare(baseline);
+    assert!(!faster.is_regression());
+    assert_eq!(faster.verdict(), "improvement");
+
+    // t ≈ 2.26 is under the threshold with fewer samples
+    let noisy = entry("c", 0, 64, 1020.0, 50.0).compare(&entry("a", 0, 64, 1000.0, 50.0));
+    assert!(!noisy.is_regression());
+    assert_eq!(noisy.verdict(), "no significant change");
+
+    // Without any spread, any change counts
+    let exact = entry("c", 0, 10, 1001.0, 0.0).compare(&entry("a", 0, 10, 1000.0, 0.0));
+    assert!(exact.is_regression());
+    let same = entry("c", 0, 10, 1000.0, 0.0).compare(&entry("a", 0, 10, 1000.0, 0.0));
+    assert_eq!(same.verdict(), "no significant change");
+}
@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+use html_escape::decode_html_entities;
+
+/// A piece of an HTML page. Just enough to read puzzle pages, which are
+/// simple and well-formed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// An opening or self-closing tag, with its name in lowercase and its
+    /// attributes unparsed.
+    Open {
+        name: String,
+        attrs: &'a str,
+    },
+    Close(String),
+    /// Text between tags, with entities decoded.
+    Text(Cow<'a, str>),
+}
+
+/// Splits `html` into tags and text. Comments and doctypes are skipped.
+pub fn tokens(html: &str) -> Tokens<'_> {
+    Tokens { rest: html }
+}
+
+pub struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            if let Some(comment) = self.rest.strip_prefix("<!--") {
+                self.rest = comment.split_once("-->").map_or("", |(_, rest)| rest);
+                continue;
+            }
+
+            let is_tag = self.rest.starts_with('<')
+                && self.rest[1..]
+                    .starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!');
+            if !is_tag {
+                // A `<` that doesn't start a tag is part of the text
+                let end = self.rest[1..].find('<').map_or(self.rest.len(), |i| i + 1);
+                let (text, rest) = self.rest.split_at(end);
+                self.rest = rest;
+                return Some(Token::Text(decode_html_entities(text)));
+            }
+
+            let Some((tag, rest)) = self.rest[1..].split_once('>') else {
+                self.rest = "";
+                return None;
+            };
+            self.rest = rest;
+            if tag.starts_with('!') {
+                continue;
+            }
+
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(Token::Close(name.trim().to_ascii_lowercase()));
+            }
+            let tag = tag.strip_suffix('/').unwrap_or(tag);
+            let name_end = tag
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(tag.len());
+            return Some(Token::Open {
+                name: tag[..name_end].to_ascii_lowercase(),
+                attrs: tag[name_end..].trim(),
+            });
+        }
+    }
+}
+
+/// The value of the attribute `name` in a tag's attributes, like `href` in
+/// `href="/2025/day/2"`.
+pub fn attr<'a>(attrs: &'a str, name: &str) -> Option<Cow<'a, str>> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let (key, after) = rest.split_at(key_end);
+        let after = after.trim_start();
+        let Some(value) = after.strip_prefix('=') else {
+            // An attribute without a value
+            rest = after;
+            continue;
+        };
+        let value = value.trim_start();
+        let (value, after) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split_once(quote)?,
+            _ => value.split_at(value.find(char::is_whitespace).unwrap_or(value.len())),
+        };
+        if key.eq_ignore_ascii_case(name) {
+            return Some(decode_html_entities(value));
+        }
+        rest = after;
+    }
+}
+
+#[test]
+fn tokens_t() {
+    let html = r#"<!DOCTYPE html><p class="x">a &lt; b<br/>c < d<!-- note --></P>"#;
+    assert_eq!(
+        tokens(html).collect::<Vec<_>>(),
+        [
+            Token::Open {
+                name: "p".to_string(),
+                attrs: r#"class="x""#
+            },
+            Token::Text("a < b".into()),
+            Token::Open {
+                name: "br".to_string(),
+                attrs: ""
+            },
+            Token::Text("c ".into()),
+            Token::Text("< d".into()),
+            Token::Close("p".to_string()),
+        ]
+    );
+
+    let attrs = r#"href="/2025/day/2?a=1&amp;b=2" target=_blank hidden title='x y'"#;
+    assert_eq!(attr(attrs, "href").unwrap(), "/2025/day/2?a=1&b=2");
+    assert_eq!(attr(attrs, "target").unwrap(), "_blank");
+    assert_eq!(attr(attrs, "title").unwrap(), "x y");
+    assert_eq!(attr(attrs, "class"), None);
+}
@@ -0,0 +1,389 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use ureq::http::{Response, StatusCode};
+use ureq::{Agent, Body, Proxy};
+
+use crate::{AocError, Res};
+
+/// User agent (see [Eric's post on the
+/// subreddit](https://www.reddit.com/r/adventofcode/comments/z9dhtd))
+const USER_AGENT: &str = "github.com/drewtato/aoc2025";
+
+/// How the client connects to the site.
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    /// Limit on a whole request, including reading the response.
+    pub timeout: Option<Duration>,
+    /// Proxy URL, like `http://localhost:3128`. When unset, the usual proxy
+    /// environment variables are used.
+    pub proxy: Option<String>,
+    pub retry: Retry,
+    /// Least time between the start of two requests.
+    pub interval: Duration,
+    /// Fail instead of making requests. Cached pages are still used.
+    pub offline: bool,
+    /// Where pages are cached. Nothing is cached when unset.
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// How many times to repeat a request that failed in a way that might not
+/// happen again.
+#[derive(Debug, Clone, Copy)]
+pub struct Retry {
+    pub retries: u32,
+    /// Wait before the first retry. Doubles after each one.
+    pub delay: Duration,
+}
+
+/// Makes every request to the site, no closer together than the interval.
+#[derive(Debug)]
+pub struct Client {
+    agent: Agent,
+    options: HttpOptions,
+    /// When the last request was started.
+    last_request: Mutex<Option<Instant>>,
+}
+
+/// A successful response, or an error response with the body that explains
+/// it.
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub status: StatusCode,
+    pub body: Vec<u8>,
+}
+
+impl Page {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Validators for a cached page, stored next to its body.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpOptions {
+    pub fn client(&self) -> Res<Client> {
+        let proxy = match &self.proxy {
+            Some(proxy) => Some(Proxy::new(proxy)?),
+            None => Proxy::try_from_env(),
+        };
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .timeout_global(self.timeout)
+            .proxy(proxy)
+            // Error statuses are checked by the caller so the body can be
+            // shown, and server errors are retried
+            .http_status_as_error(false)
+            .build()
+            .new_agent();
+        Ok(Client {
+            agent,
+            options: self.clone(),
+            last_request: Mutex::new(None),
+        })
+    }
+}
+
+impl Client {
+    /// Waits until the next request is allowed.
+    fn throttle(&self, url: &str) -> Res<()> {
+        if self.options.offline {
+            return Err(AocError::offline(url));
+        }
+        let mut last = self.last_request.lock().unwrap();
+        if let Some(last) = *last {
+            let wait = self.options.interval.saturating_sub(last.elapsed());
+            if !wait.is_zero() {
+                std::thread::sleep(wait);
+            }
+        }
+        *last = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Gets `url` with the session cookie, retrying connection failures,
+    /// timeouts, and server errors.
+    pub fn get(&self, url: &str, session: &str) -> Res<Response<Body>> {
+        self.get_with(url, session, &[])
+    }
+
+    fn get_with(&self, url: &str, session: &str, headers: &[(&str, &str)]) -> Res<Response<Body>> {
+        let retry = self.options.retry;
+        let cookie = format!("session={session}");
+        let mut delay = retry.delay;
+        let mut attempt = 0;
+        loop {
+            self.throttle(url)?;
+            let mut request = self.agent.get(url).header("cookie", &cookie);
+            for &(name, value) in headers {
+                request = request.header(name, value);
+            }
+            let result = request.call();
+            let reason = match &result {
+                Ok(response) if response.status().is_server_error() => {
+                    response.status().to_string()
+                }
+                Err(e) if is_transient(e) => e.to_string(),
+                _ => break result,
+            };
+            if attempt == retry.retries {
+                break result;
+            }
+            attempt += 1;
+            eprintln!(
+                "Fetching {url} failed ({reason}), retrying in {delay:?} ({attempt}/{})",
+                retry.retries
+            );
+            std::thread::sleep(delay);
+            delay *= 2;
+        }
+        .map_err(Into::into)
+    }
+
+    /// Gets `url` like [`Client::get`], but asks the site to skip sending it
+    /// again if it hasn't changed since it was cached. Offline, the cached page
+    /// is used as is, and `session` is never asked for the token.
+    ///
+    /// Pages are cached by URL, so each account needs its own cache directory.
+    pub fn get_cached(&self, url: &str, session: impl FnOnce() -> Res<String>) -> Res<Page> {
+        let Some(dir) = &self.options.cache_dir else {
+            return read_page(self.get(url, &session()?)?);
+        };
+        let key = format!("{:016x}", fnv1a(url.as_bytes()));
+        let entry_path = dir.join(format!("{key}.json"));
+        let body_path = dir.join(format!("{key}.body"));
+
+        let entry: Option<CacheEntry> = match std::fs::read(&entry_path) {
+            Ok(entry) => serde_json::from_slice(&entry).ok(),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let cached = match std::fs::read(&body_path) {
+            Ok(body) => Some(Page {
+                status: StatusCode::OK,
+                body,
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        if self.options.offline {
+            return cached.ok_or_else(|| AocError::offline(url));
+        }
+
+        // Without the body there's nothing to fall back on if it's unchanged
+        let mut headers = Vec::new();
+        if let Some(entry) = entry.as_ref().filter(|_| cached.is_some()) {
+            if let Some(etag) = &entry.etag {
+                headers.push(("if-none-match", etag.as_str()));
+            }
+            if let Some(modified) = &entry.last_modified {
+                headers.push(("if-modified-since", modified.as_str()));
+            }
+        }
+        let response = self.get_with(url, &session()?, &headers)?;
+        if response.status() == StatusCode::NOT_MODIFIED
+            && let Some(page) = cached
+        {
+            return Ok(page);
+        }
+
+        let header = |name| {
+            let value = response.headers().get(name)?.to_str().ok()?;
+            Some(value.to_string())
+        };
+        let entry = CacheEntry {
+            url: url.to_string(),
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+        };
+        let page = read_page(response)?;
+        if page.status.is_success() {
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(&body_path, &page.body)?;
+            std::fs::write(&entry_path, serde_json::to_vec(&entry)?)?;
+        }
+        Ok(page)
+    }
+
+    /// Posts a form with the session cookie. Never retried, since the site may
+    /// have acted on it.
+    pub fn post_form(
+        &self,
+        url: &str,
+        session: &str,
+        form: [(&str, &str); 2],
+    ) -> Res<Response<Body>> {
+        self.throttle(url)?;
+        Ok(self
+            .agent
+            .post(url)
+            .header("cookie", &format!("session={session}"))
+            .send_form(form)?)
+    }
+}
+
+fn read_page(response: Response<Body>) -> Res<Page> {
+    let status = response.status();
+    let body = response.into_body().read_to_vec()?;
+    Ok(Page { status, body })
+}
+
+/// Whether a request that failed this way is worth trying again.
+fn is_transient(error: &ureq::Error) -> bool {
+    matches!(
+        error,
+        ureq::Error::Io(_)
+            | ureq::Error::Timeout(_)
+            | ureq::Error::ConnectionFailed
+            | ureq::Error::ConnectProxyFailed(_)
+            | ureq::Error::BodyStalled
+    )
+}
+
+/// FNV-1a, for cache file names that stay the same between builds.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+pub(crate) fn test_options() -> HttpOptions {
+    HttpOptions {
+        timeout: Some(Duration::from_secs(5)),
+        proxy: None,
+        retry: Retry {
+            retries: 1,
+            delay: Duration::ZERO,
+        },
+        interval: Duration::ZERO,
+        offline: false,
+        cache_dir: None,
+    }
+}
+
+/// Answers each connection with the next response, and returns the requests
+/// it got, with the head and body separated by a blank line.
+#[cfg(test)]
+pub(crate) fn serve(responses: Vec<String>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request += &line;
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request += &String::from_utf8_lossy(&body);
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (base_url, server)
+}
+
+#[test]
+fn get_retries_t() {
+    let response =
+        |status| format!("HTTP/1.1 {status}\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok");
+    let (base_url, server) = serve(vec![
+        response("503 Service Unavailable"),
+        response("503 Service Unavailable"),
+        response("200 OK"),
+    ]);
+    let url = format!("{base_url}/2025/day/1/input");
+
+    let client = test_options().client().unwrap();
+
+    // One retry isn't enough to get past two failures
+    let response = client.get(&url, "abc").unwrap();
+    assert_eq!(response.status(), 503);
+
+    let response = client.get(&url, "abc").unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.into_body().read_to_string().unwrap(), "ok");
+
+    server.join().unwrap();
+}
+
+#[test]
+fn get_cached_t() {
+    let (base_url, server) = serve(vec![
+        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello"
+            .to_string(),
+        "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_string(),
+        "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello"
+            .to_string(),
+    ]);
+    let url = format!("{base_url}/2025/day/1");
+    let dir = std::env::temp_dir().join(format!("aoc-http-cache-{}", std::process::id()));
+
+    let mut options = HttpOptions {
+        cache_dir: Some(dir.clone()),
+        interval: Duration::from_millis(50),
+        ..test_options()
+    };
+    let client = options.client().unwrap();
+    let session = || Ok("abc".to_string());
+    let start = Instant::now();
+    assert_eq!(client.get_cached(&url, session).unwrap().body, b"hello");
+    assert_eq!(client.get_cached(&url, session).unwrap().body, b"hello");
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    // A lost body is fetched in full again
+    for entry in std::fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "body") {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+    assert_eq!(client.get_cached(&url, session).unwrap().body, b"hello");
+
+    let heads = server.join().unwrap();
+    assert!(!heads[0].contains("if-none-match"));
+    assert!(heads[1].contains("if-none-match: \"v1\""));
+    assert!(!heads[2].contains("if-none-match"));
+
+    options.offline = true;
+    let client = options.client().unwrap();
+    // Offline, the session token isn't needed
+    let no_session = || Err(AocError::no_session("nowhere"));
+    assert_eq!(client.get_cached(&url, no_session).unwrap().body, b"hello");
+    assert!(matches!(
+        client.get_cached(&format!("{base_url}/2025/day/2"), no_session),
+        Err(AocError::Offline { .. })
+    ));
+    assert!(matches!(
+        client.get(&url, "abc"),
+        Err(AocError::Offline { .. })
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,37 @@
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::Res;
+
+/// Reads every value in a file with one JSON value per line. A missing file
+/// has no values.
+pub fn read_json_lines<T: DeserializeOwned>(path: &Path) -> Res<Vec<T>> {
+    let file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut values = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        values.push(serde_json::from_str(&line)?);
+    }
+    Ok(values)
+}
+
+/// Adds a value to the end of a file with one JSON value per line.
+pub fn append_json_line<T: Serialize>(path: &Path, value: &T) -> Res<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    file.write_all(&line)?;
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use crate::Res;
+
+/// How long a fetched leaderboard is used before fetching it again. The site
+/// asks for no more than one request every 15 minutes.
+pub const LEADERBOARD_REFRESH_SECS: u64 = 15 * 60;
+
+/// A private leaderboard, as returned by
+/// `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    /// When day 1 was released, as a Unix timestamp. Older responses don't
+    /// have it.
+    #[serde(default)]
+    pub day1_ts: Option<i64>,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for anonymous members.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    #[serde(default)]
+    pub last_star_ts: i64,
+    /// Stars by day and then part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    fn star_ts(&self, day: u32, part: u32) -> Option<i64> {
+        Some(self.completion_day_level.get(&day)?.get(&part)?.get_star_ts)
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Res<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Members by score, then by stars, then by who got their last star
+    /// first, like the site orders them.
+    fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| {
+            (
+                std::cmp::Reverse(m.local_score),
+                std::cmp::Reverse(m.stars),
+                m.last_star_ts,
+            )
+        });
+        members
+    }
+
+    /// The latest day anyone has a star on.
+    pub fn last_day(&self) -> Option<u32> {
+        self.members
+            .values()
+            .filter_map(|m| m.completion_day_level.keys().max().copied())
+            .max()
+    }
+
+    /// The ranked table, with a column for each of `days` days: `*` for both
+    /// stars and `.` for only the first.
+    pub fn table(&self, days: u32) -> String {
+        let mut out = String::new();
+        let numbers: String = (1..=days)
+            .map(|day| char::from(b'0' + (day % 10) as u8))
+            .collect();
+        writeln!(out, "       score stars  {numbers}").unwrap();
+        for (rank, member) in self.ranked().into_iter().enumerate() {
+            let strip: String = (1..=days)
+                .map(|day| match member.completion_day_level.get(&day) {
+                    Some(parts) if parts.len() >= 2 => '*',
+                    Some(parts) if !parts.is_empty() => '.',
+                    _ => ' ',
+                })
+                .collect();
+            writeln!(
+                out,
+                "{:>4}) {:>6} {:>5}  {strip}  {}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.name()
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// Everyone with a star on `day`, in the order they finished, with how
+    /// long after `release` each star came and the time between the two.
+    pub fn day_table(&self, day: u32, release: i64) -> String {
+        let mut members: Vec<(&Member, i64, Option<i64>)> = self
+            .members
+            .values()
+            .filter_map(|m| Some((m, m.star_ts(day, 1)?, m.star_ts(day, 2))))
+            .collect();
+        // Both stars first, by when the second came, then the rest
+        members.sort_by_key(|&(_, first, second)| (second.is_none(), second.unwrap_or(first)));
+
+        let mut out = String::new();
+        writeln!(out, "Day {day}").unwrap();
+        writeln!(
+            out,
+            "{:>4}  {:>9}  {:>9}  {:>9}  name",
+            "", "part 1", "part 2", "delta"
+        )
+        .unwrap();
+        for (rank, (member, first, second)) in members.into_iter().enumerate() {
+            let (second, delta) = match second {
+                Some(second) => (
+                    clock(second - release),
+                    format!("+{}", clock(second - first)),
+                ),
+                None => (String::new(), String::new()),
+            };
+            writeln!(
+                out,
+                "{:>4}) {:>9}  {second:>9}  {delta:>9}  {}",
+                rank + 1,
+                clock(first - release),
+                member.name()
+            )
+            .unwrap();
+        }
+        out
+    }
+}
+
+/// Seconds as `HH:MM:SS`, with as many hour digits as needed.
+fn clock(secs: i64) -> String {
+    let secs = secs.max(0);
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[test]
+fn leaderboard_t() {
+    let board = Leaderboard::parse(include_str!("../fixtures/leaderboard.json")).unwrap();
+    assert_eq!(board.day1_ts, Some(1764565200));
+    assert_eq!(board.last_day(), Some(2));
+
+    assert_eq!(
+        board.table(3),
+        "       score stars  123
+   1)     11     4  **   Ada
+   2)      9     3  *.   (anonymous user #202)
+   3)      0     0       Grace
+"
+    );
+
+    let day2 = board.day1_ts.unwrap() + 24 * 60 * 60;
+    assert_eq!(
+        board.day_table(2, day2),
+        "Day 2
+         part 1     part 2      delta  name
+   1)  00:22:00   01:20:00  +00:58:00  Ada
+   2)  00:23:20                        (anonymous user #202)
+"
+    );
+}
@@ -1,6 +1,24 @@
+mod build;
+mod config;
+#[cfg(feature = "in-process")]
+mod days;
 mod error;
 pub use error::AocError;
+mod examples;
+mod history;
+mod html;
+mod http;
+mod jsonl;
+mod leaderboard;
+pub mod output;
+mod personal;
+mod render;
 pub mod runner;
+mod scaffold;
+mod session;
+mod stats;
+mod submit;
+pub mod test_inputs;
 
 pub type Res<T> = Result<T, AocError>;
 pub use runner::Settings;
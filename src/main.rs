@@ -1,11 +1,10 @@
 use std::process::exit;
 
 use aoc2025::Settings;
-use clap::Parser;
 
 fn main() {
-    let mut settings = Settings::parse();
-    if let Err(e) = settings.run() {
+    let result = Settings::from_args().and_then(|mut settings| settings.run());
+    if let Err(e) = result {
         eprintln!("Runner: {e}");
         exit(1);
     }
@@ -0,0 +1,213 @@
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+use solver_interface::AllocStats;
+
+use crate::Res;
+
+/// Format for the results printed to stdout with `--output-format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Only print the human-readable text to stderr.
+    #[default]
+    Text,
+    /// Print one JSON object per line.
+    Json,
+    /// Print comma-separated values with a header row.
+    Csv,
+}
+
+/// One day and part's result. Fields that don't apply to the current mode are
+/// left as `None`.
+///
+/// Durations are in nanoseconds. Parse times are reported as part 0. Heap
+/// usage is only reported with `--alloc-stats`.
+#[derive(Debug, Default, Serialize)]
+pub struct Record<'a> {
+    pub mode: &'static str,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub test: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub samples: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stddev_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mad_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ci_low_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ci_high_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outliers: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocated_bytes: Option<u64>,
+}
+
+const CSV_HEADER: &str = "mode,year,day,part,test,answer,duration_ns,correct,samples,avg_ns,\
+    median_ns,min_ns,max_ns,stddev_ns,mad_ns,ci_low_ns,ci_high_ns,outliers,\
+    peak_bytes,allocations,allocated_bytes";
+
+impl Record<'_> {
+    /// A record with only the heap usage filled in.
+    pub fn with_alloc(alloc: Option<AllocStats>) -> Self {
+        Self {
+            peak_bytes: alloc.map(|a| a.peak_bytes),
+            allocations: alloc.map(|a| a.allocations),
+            allocated_bytes: alloc.map(|a| a.allocated_bytes),
+            ..Default::default()
+        }
+    }
+
+    fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        fn opt<W: Write, T: std::fmt::Display>(w: &mut W, t: Option<T>) -> std::io::Result<()> {
+            match t {
+                Some(t) => write!(w, ",{t}"),
+                None => write!(w, ","),
+            }
+        }
+
+        write!(
+            writer,
+            "{},{},{},{},{},",
+            self.mode, self.year, self.day, self.part, self.test
+        )?;
+        if let Some(answer) = self.answer {
+            write_csv_field(&mut writer, answer)?;
+        }
+        opt(&mut writer, self.duration_ns)?;
+        opt(&mut writer, self.correct)?;
+        opt(&mut writer, self.samples)?;
+        opt(&mut writer, self.avg_ns)?;
+        opt(&mut writer, self.median_ns)?;
+        opt(&mut writer, self.min_ns)?;
+        opt(&mut writer, self.max_ns)?;
+        opt(&mut writer, self.stddev_ns)?;
+        opt(&mut writer, self.mad_ns)?;
+        opt(&mut writer, self.ci_low_ns)?;
+        opt(&mut writer, self.ci_high_ns)?;
+        opt(&mut writer, self.outliers)?;
+        opt(&mut writer, self.peak_bytes)?;
+        opt(&mut writer, self.allocations)?;
+        opt(&mut writer, self.allocated_bytes)?;
+        writeln!(writer)
+    }
+}
+
+/// Quotes the field if it contains anything that would break the row.
+fn write_csv_field<W: Write>(mut writer: W, field: &str) -> std::io::Result<()> {
+    if field.contains([',', '"', '\n', '\r']) {
+        write!(writer, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        write!(writer, "{field}")
+    }
+}
+
+/// Writes records to stdout in the selected format.
+#[derive(Debug, Default)]
+pub(crate) struct RecordWriter {
+    wrote_header: bool,
+}
+
+impl RecordWriter {
+    pub(crate) fn write(&mut self, format: OutputFormat, record: &Record) -> Res<()> {
+        self.write_to(std::io::stdout().lock(), format, record)
+    }
+
+    fn write_to<W: Write>(&mut self, mut out: W, format: OutputFormat, record: &Record) -> Res<()> {
+        match format {
+            OutputFormat::Text => return Ok(()),
+            OutputFormat::Json => {
+                serde_json::to_writer(&mut out, record)?;
+                writeln!(out)?;
+            }
+            OutputFormat::Csv => {
+                if !self.wrote_header {
+                    writeln!(out, "{CSV_HEADER}")?;
+                    self.wrote_header = true;
+                }
+                record.write_csv(&mut out)?;
+            }
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+#[test]
+fn csv_t() {
+    let mut writer = RecordWriter::default();
+    let mut out = Vec::new();
+    let record = |answer| Record {
+        mode: "run",
+        year: 2025,
+        day: 3,
+        part: 1,
+        answer,
+        duration_ns: Some(1500),
+        ..Default::default()
+    };
+    for answer in [
+        Some("plain"),
+        Some("a,b"),
+        Some("say \"hi\""),
+        Some("#.\n.#"),
+        None,
+    ] {
+        writer
+            .write_to(&mut out, OutputFormat::Csv, &record(answer))
+            .unwrap();
+    }
+    let bench = Record {
+        mode: "bench",
+        samples: Some(10),
+        allocated_bytes: Some(64),
+        ..Default::default()
+    };
+    writer
+        .write_to(&mut out, OutputFormat::Csv, &bench)
+        .unwrap();
+
+    let empty = ",".repeat(14);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        format!(
+            "{CSV_HEADER}\n\
+             run,2025,3,1,0,plain,1500{empty}\n\
+             run,2025,3,1,0,\"a,b\",1500{empty}\n\
+             run,2025,3,1,0,\"say \"\"hi\"\"\",1500{empty}\n\
+             run,2025,3,1,0,\"#.\n.#\",1500{empty}\n\
+             run,2025,3,1,0,,1500{empty}\n\
+             bench,0,0,0,0,,,,10,,,,,,,,,,,,64\n"
+        )
+    );
+    // Every row has a field for each column
+    assert_eq!(CSV_HEADER.split(',').count(), 21);
+    assert!(!CSV_HEADER.contains(' '));
+
+    let mut out = Vec::new();
+    writer
+        .write_to(&mut out, OutputFormat::Text, &bench)
+        .unwrap();
+    assert!(out.is_empty());
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::time::Duration;
+
+use crate::html::{Token, tokens};
+
+/// Times from the personal leaderboard page, `/{year}/leaderboard/self`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalStats {
+    /// Days with at least one star, latest first like the site lists them.
+    pub days: Vec<DayStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStats {
+    pub day: u32,
+    pub parts: [Option<PartStats>; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartStats {
+    pub time: SolveTime,
+    /// Only given for years with a global leaderboard.
+    pub rank: Option<u32>,
+    pub score: Option<u32>,
+}
+
+/// How long after release a star was earned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveTime {
+    Secs(u32),
+    /// The site doesn't say how long past a day.
+    OverADay,
+}
+
+impl Display for SolveTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match *self {
+            SolveTime::Secs(secs) => {
+                format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+            }
+            SolveTime::OverADay => ">24h".to_string(),
+        };
+        f.pad(&text)
+    }
+}
+
+impl SolveTime {
+    fn parse(time: &str) -> Option<Self> {
+        if time == ">24h" {
+            return Some(SolveTime::OverADay);
+        }
+        let mut secs = 0;
+        for field in time.split(':') {
+            secs = secs * 60 + field.parse::<u32>().ok()?;
+        }
+        Some(SolveTime::Secs(secs))
+    }
+}
+
+impl PersonalStats {
+    /// Reads the table in the page's `<pre>`. Rows are either a time for each
+    /// part, or a time, rank, and score for each part. Parts without a star
+    /// are shown as `-`.
+    pub fn parse(html: &str) -> Self {
+        let mut text = String::new();
+        let mut in_pre = false;
+        for token in tokens(html) {
+            match token {
+                Token::Open { name, .. } if name == "pre" => in_pre = true,
+                Token::Close(name) if name == "pre" => in_pre = false,
+                Token::Text(t) if in_pre => text += &t,
+                _ => (),
+            }
+        }
+
+        let days = text
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                let (&day, rest) = fields.split_first()?;
+                let day = day.parse().ok()?;
+                let per_part = match rest.len() {
+                    2 => 1,
+                    6 => 3,
+                    _ => return None,
+                };
+                let part = |fields: &[&str]| {
+                    Some(PartStats {
+                        time: SolveTime::parse(fields[0])?,
+                        rank: fields.get(1).and_then(|r| r.parse().ok()),
+                        score: fields.get(2).and_then(|s| s.parse().ok()),
+                    })
+                };
+                let (first, second) = rest.split_at(per_part);
+                Some(DayStats {
+                    day,
+                    parts: [part(first), part(second)],
+                })
+            })
+            .collect();
+        PersonalStats { days }
+    }
+
+    /// The table for the README. With `bench`, each part's latest benchmark
+    /// median is added, keyed by day and part.
+    pub fn table(&self, bench: Option<&BTreeMap<(u32, u32), Duration>>) -> String {
+        let mut out = String::from("Day   -Part 1-   -Part 2-");
+        if bench.is_some() {
+            out += "   -Bench 1-   -Bench 2-";
+        }
+        out.push('\n');
+
+        for day in &self.days {
+            let time = |part: usize| {
+                day.parts[part]
+                    .as_ref()
+                    .map_or("-".to_string(), |p| p.time.to_string())
+            };
+            write!(out, "{:>3}   {:>8}   {:>8}", day.day, time(0), time(1)).unwrap();
+            if let Some(bench) = bench {
+                for part in [1, 2] {
+                    let median = bench
+                        .get(&(day.day, part))
+                        .map_or("-".to_string(), |d| format!("{d:.2?}"));
+                    write!(out, "   {median:>9}").unwrap();
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Replaces what's between the `<!-- {marker}:start -->` and
+/// `<!-- {marker}:end -->` lines in `text` with `content`. Returns `None` if
+/// the markers aren't there.
+pub fn replace_block(text: &str, marker: &str, content: &str) -> Option<String> {
+    let start = format!("<!-- {marker}:start -->\n");
+    let end = format!("<!-- {marker}:end -->");
+    let block_start = text.find(&start)? + start.len();
+    let block_end = block_start + text[block_start..].find(&end)?;
+    Some(format!(
+        "{}{content}{}",
+        &text[..block_start],
+        &text[block_end..]
+    ))
+}
+
+#[test]
+fn personal_stats_t() {
+    let page = r#"<main><article><p>These are your personal leaderboard statistics.</p>
+<pre>Day   <span class="leaderboard-daydesc-first">-Part 1-</span>   <span class="leaderboard-daydesc-both">-Part 2-</span>
+ 10       &gt;24h          -
+  9   00:21:42       &gt;24h
+  8   00:50:06   00:55:56
+</pre></article></main>"#;
+    let stats = PersonalStats::parse(page);
+    assert_eq!(stats.days.len(), 3);
+    assert_eq!(stats.days[0].parts[1], None);
+    assert_eq!(
+        stats.days[1].parts[0],
+        Some(PartStats {
+            time: SolveTime::Secs(21 * 60 + 42),
+            rank: None,
+            score: None,
+        })
+    );
+
+    let bench = BTreeMap::from([((8, 1), Duration::from_micros(1500))]);
+    assert_eq!(
+        stats.table(None),
+        "\
+Day   -Part 1-   -Part 2-
+ 10       >24h          -
+  9   00:21:42       >24h
+  8   00:50:06   00:55:56
+"
+    );
+    assert!(
+        stats
+            .table(Some(&bench))
+            .ends_with(" 8   00:50:06   00:55:56      1.50ms           -\n")
+    );
+
+    let old = "<pre>      <span>--------Part 1--------</span>\n\
+        Day   <span>    Time   Rank  Score</span>   <span>    Time   Rank  Score</span>\n \
+        25   00:34:15   1234      0          -      -      -\n</pre>";
+    let stats = PersonalStats::parse(old);
+    assert_eq!(stats.days[0].day, 25);
+    assert_eq!(stats.days[0].parts[0].as_ref().unwrap().rank, Some(1234));
+    assert_eq!(stats.days[0].parts[1], None);
+
+    let readme = "# Title\n<!-- stats:start -->\nold\n<!-- stats:end -->\nmore\n";
+    assert_eq!(
+        replace_block(readme, "stats", "new\n").unwrap(),
+        "# Title\n<!-- stats:start -->\nnew\n<!-- stats:end -->\nmore\n"
+    );
+    assert_eq!(replace_block("no markers", "stats", ""), None);
+}
@@ -0,0 +1,469 @@
+use crate::html::{Token, attr, tokens};
+
+/// Inline styles from `<em>` and `<code>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    em: bool,
+    code: bool,
+}
+
+/// Text in one style, and the number of the link it's in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Run {
+    text: String,
+    style: Style,
+    link: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    Heading(Vec<Run>),
+    Paragraph(Vec<Run>),
+    Code(String),
+    List(Vec<Vec<Run>>),
+}
+
+/// The description of each part from a puzzle page, ready to be shown.
+#[derive(Debug, Default)]
+pub struct Prompt {
+    parts: Vec<Vec<Block>>,
+    /// Link targets, numbered from 1 in the order they appear.
+    links: Vec<String>,
+}
+
+impl Prompt {
+    /// Reads the `<article>` for each part of a puzzle page. Links are made
+    /// absolute against `page_url`, the address the page came from.
+    pub fn parse(html: &str, page_url: &str) -> Self {
+        let mut prompt = Prompt::default();
+        let mut blocks = Vec::new();
+        let mut runs = Vec::new();
+        let mut items = Vec::new();
+        let mut in_article = false;
+        let mut pre: Option<String> = None;
+        let mut lists = 0;
+        let mut style = (0u32, 0u32);
+        let mut link = None;
+
+        for token in tokens(html) {
+            match token {
+                Token::Open { name, .. } if name == "article" => {
+                    in_article = true;
+                    blocks = Vec::new();
+                }
+                Token::Close(name) if name == "article" => {
+                    in_article = false;
+                    end_paragraph(&mut blocks, &mut runs);
+                    prompt.parts.push(std::mem::take(&mut blocks));
+                }
+                _ if !in_article => (),
+                Token::Open { name, attrs } => match &*name {
+                    "h1" | "h2" | "h3" | "pre" => {
+                        end_paragraph(&mut blocks, &mut runs);
+                        if name == "pre" {
+                            pre = Some(String::new());
+                        }
+                    }
+                    "p" if lists == 0 => end_paragraph(&mut blocks, &mut runs),
+                    "ul" | "ol" => {
+                        if lists == 0 {
+                            end_paragraph(&mut blocks, &mut runs);
+                        }
+                        lists += 1;
+                    }
+                    "li" => end_item(&mut items, &mut runs),
+                    "em" => style.0 += 1,
+                    "code" => style.1 += 1,
+                    "a" => {
+                        if let Some(href) = attr(attrs, "href") {
+                            prompt.links.push(absolute(&href, page_url));
+                            link = Some(prompt.links.len());
+                        }
+                    }
+                    "br" => push_text(&mut runs, " ", Style::default(), link),
+                    _ => (),
+                },
+                Token::Close(name) => match &*name {
+                    "h1" | "h2" | "h3" => blocks.push(Block::Heading(std::mem::take(&mut runs))),
+                    "p" if lists == 0 => end_paragraph(&mut blocks, &mut runs),
+                    "pre" => blocks.extend(pre.take().map(Block::Code)),
+                    "li" => end_item(&mut items, &mut runs),
+                    "ul" | "ol" => {
+                        end_item(&mut items, &mut runs);
+                        lists -= 1;
+                        if lists == 0 {
+                            blocks.push(Block::List(std::mem::take(&mut items)));
+                        }
+                    }
+                    "em" => style.0 = style.0.saturating_sub(1),
+                    "code" => style.1 = style.1.saturating_sub(1),
+                    "a" => link = None,
+                    _ => (),
+                },
+                Token::Text(text) => match &mut pre {
+                    Some(pre) => pre.push_str(&text),
+                    None => {
+                        let style = Style {
+                            em: style.0 > 0,
+                            code: style.1 > 0,
+                        };
+                        push_text(&mut runs, &text, style, link);
+                    }
+                },
+            }
+        }
+        prompt
+    }
+
+    /// How many parts are shown, which is 2 once part 1 is solved.
+    pub fn parts(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// The prompt as text wrapped to `width` columns, with emphasis in bold
+    /// when `styled`. Links are numbered and listed at the end.
+    pub fn text(&self, width: usize, styled: bool) -> String {
+        let mut out = String::new();
+        for block in self.parts.iter().flatten() {
+            match block {
+                Block::Heading(runs) => {
+                    let runs: Vec<Run> = runs
+                        .iter()
+                        .map(|run| Run {
+                            style: Style {
+                                em: true,
+                                ..run.style
+                            },
+                            ..run.clone()
+                        })
+                        .collect();
+                    out += &wrap(&runs, width, "", "", styled);
+                }
+                Block::Paragraph(runs) => out += &wrap(runs, width, "", "", styled),
+                Block::Code(code) => {
+                    for line in code.trim_end_matches('\n').lines() {
+                        out += "    ";
+                        out += line;
+                        out.push('\n');
+                    }
+                }
+                Block::List(items) => {
+                    for item in items {
+                        out += &wrap(item, width, "  - ", "    ", styled);
+                    }
+                }
+            }
+            out.push('\n');
+        }
+        for (i, link) in self.links.iter().enumerate() {
+            out += &format!("[{}] {link}\n", i + 1);
+        }
+        out
+    }
+
+    /// The prompt as Markdown.
+    pub fn markdown(&self) -> String {
+        let mut out = String::new();
+        for block in self.parts.iter().flatten() {
+            match block {
+                Block::Heading(runs) => out += &format!("## {}\n", self.inline_markdown(runs)),
+                Block::Paragraph(runs) => out += &format!("{}\n", self.inline_markdown(runs)),
+                Block::Code(code) => {
+                    out += "```\n";
+                    out += code.trim_end_matches('\n');
+                    out += "\n```\n";
+                }
+                Block::List(items) => {
+                    for item in items {
+                        out += &format!("- {}\n", self.inline_markdown(item));
+                    }
+                }
+            }
+            out.push('\n');
+        }
+        out.pop();
+        out
+    }
+
+    fn inline_markdown(&self, runs: &[Run]) -> String {
+        let mut out = String::new();
+        for (i, run) in runs.iter().enumerate() {
+            if run.link.is_some() && (i == 0 || runs[i - 1].link != run.link) {
+                out.push('[');
+            }
+
+            let core = run.text.trim();
+            if core.is_empty() {
+                out += &run.text;
+            } else {
+                let lead = &run.text[..run.text.len() - run.text.trim_start().len()];
+                let trail = &run.text[run.text.trim_end().len()..];
+                let mut core = if run.style.code {
+                    let ticks = if core.contains('`') { "``" } else { "`" };
+                    format!("{ticks}{core}{ticks}")
+                } else {
+                    escape_markdown(core)
+                };
+                if run.style.em {
+                    core = format!("**{core}**");
+                }
+                out += lead;
+                out += &core;
+                out += trail;
+            }
+
+            if let Some(link) = run.link
+                && runs.get(i + 1).is_none_or(|next| next.link != run.link)
+            {
+                out += &format!("]({})", self.links[link - 1]);
+            }
+        }
+        out.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Adds a paragraph for the text so far, unless it's only whitespace.
+fn end_paragraph(blocks: &mut Vec<Block>, runs: &mut Vec<Run>) {
+    if runs.iter().any(|run| !run.text.trim().is_empty()) {
+        blocks.push(Block::Paragraph(std::mem::take(runs)));
+    } else {
+        runs.clear();
+    }
+}
+
+/// Adds a list item for the text so far, unless it's only whitespace.
+fn end_item(items: &mut Vec<Vec<Run>>, runs: &mut Vec<Run>) {
+    if runs.iter().any(|run| !run.text.trim().is_empty()) {
+        items.push(std::mem::take(runs));
+    } else {
+        runs.clear();
+    }
+}
+
+/// Adds text with whitespace collapsed like a browser does.
+fn push_text(runs: &mut Vec<Run>, text: &str, style: Style, link: Option<usize>) {
+    let mut collapsed = String::with_capacity(text.len());
+    for c in text.chars() {
+        if !c.is_whitespace() {
+            collapsed.push(c);
+        } else if !collapsed.ends_with(' ') {
+            collapsed.push(' ');
+        }
+    }
+    match runs.last_mut() {
+        Some(last) if last.style == style && last.link == link => {
+            if last.text.ends_with(' ') && collapsed.starts_with(' ') {
+                collapsed.remove(0);
+            }
+            last.text += &collapsed;
+        }
+        _ => runs.push(Run {
+            text: collapsed,
+            style,
+            link,
+        }),
+    }
+}
+
+/// Resolves a link on the page at `page_url`, which has no query or fragment.
+fn absolute(href: &str, page_url: &str) -> String {
+    let (scheme, rest) = page_url.split_once("://").unwrap_or(("https", page_url));
+    let host = rest.split('/').next().unwrap_or_default();
+    let path = &rest[host.len()..];
+
+    // Anything with a scheme, like `https:` or `mailto:`, is already absolute
+    let scheme_end = href.find(|c: char| !c.is_ascii_alphanumeric() && !"+-.".contains(c));
+    if scheme_end.is_some_and(|i| i > 0 && href[i..].starts_with(':')) {
+        return href.to_string();
+    }
+    if let Some(rest) = href.strip_prefix("//") {
+        return format!("{scheme}://{rest}");
+    }
+    if href.is_empty() || href.starts_with(['#', '?']) {
+        return format!("{page_url}{href}");
+    }
+
+    let (href_path, suffix) = href.split_at(href.find(['?', '#']).unwrap_or(href.len()));
+    let joined = if href_path.starts_with('/') {
+        href_path.to_string()
+    } else {
+        let dir = &path[..path.rfind('/').map_or(0, |i| i + 1)];
+        format!("/{}{href_path}", dir.trim_start_matches('/'))
+    };
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in joined.split('/').skip(1) {
+        match segment {
+            "." => (),
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    format!("{scheme}://{host}/{}{suffix}", segments.join("/"))
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Wraps text to `width` columns, starting with `first` and indenting the
+/// lines after it with `indent`. Words are only broken at whitespace.
+fn wrap(runs: &[Run], width: usize, first: &str, indent: &str, styled: bool) -> String {
+    // Each word is its text with any styling, and how many columns it takes
+    let mut words: Vec<(String, usize)> = Vec::new();
+    let mut in_word = false;
+    for (i, run) in runs.iter().enumerate() {
+        let mut piece = String::new();
+        let flush = |piece: &mut String, words: &mut Vec<(String, usize)>| {
+            if let Some(word) = words.last_mut().filter(|_| !piece.is_empty()) {
+                word.0 += &styled_text(piece, run.style, styled);
+                word.1 += piece.chars().count();
+                piece.clear();
+            }
+        };
+        for c in run.text.chars() {
+            if c.is_whitespace() {
+                flush(&mut piece, &mut words);
+                in_word = false;
+            } else {
+                if !in_word {
+                    words.push((String::new(), 0));
+                    in_word = true;
+                }
+                piece.push(c);
+            }
+        }
+        flush(&mut piece, &mut words);
+
+        // Links are marked after their last word
+        if let Some(link) = run.link
+            && runs.get(i + 1).is_none_or(|next| next.link != run.link)
+            && let Some(word) = words.last_mut()
+        {
+            let mark = format!("[{link}]");
+            word.1 += mark.len();
+            word.0 += &mark;
+        }
+    }
+
+    let mut out = first.to_string();
+    let mut column = first.len();
+    let mut line_start = true;
+    for (word, len) in words {
+        if !line_start && column + 1 + len > width {
+            out.push('\n');
+            out += indent;
+            column = indent.len();
+            line_start = true;
+        }
+        if !line_start {
+            out.push(' ');
+            column += 1;
+        }
+        out += &word;
+        column += len;
+        line_start = false;
+    }
+    out.push('\n');
+    out
+}
+
+fn styled_text(text: &str, style: Style, styled: bool) -> String {
+    if styled && style.em {
+        format!("\x1b[1m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+#[test]
+fn absolute_t() {
+    let page = "https://adventofcode.com/2025/day/1";
+    for (href, url) in [
+        ("1/input", "https://adventofcode.com/2025/day/1/input"),
+        ("#part2", "https://adventofcode.com/2025/day/1#part2"),
+        ("/2025/about", "https://adventofcode.com/2025/about"),
+        (
+            "../leaderboard",
+            "https://adventofcode.com/2025/leaderboard",
+        ),
+        ("./2?x=1#y", "https://adventofcode.com/2025/day/2?x=1#y"),
+        ("//example.com/a", "https://example.com/a"),
+        (
+            "https://en.wikipedia.org/wiki/Tree",
+            "https://en.wikipedia.org/wiki/Tree",
+        ),
+        ("mailto:someone@example.com", "mailto:someone@example.com"),
+    ] {
+        assert_eq!(absolute(href, page), url, "{href}");
+    }
+    assert_eq!(
+        absolute("2/input", "http://127.0.0.1:8767/2022/day/2"),
+        "http://127.0.0.1:8767/2022/day/2/input"
+    );
+}
+
+#[test]
+fn render_t() {
+    let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>The elves need <em>fifty</em> stars, see <a href="/2025/day/1/input">your
+input</a>.</p>
+<pre><code>1 2
+3 4
+</code></pre>
+<ul>
+<li>One <code>item</code> in a list that is long enough to wrap.</li>
+<li>Two</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>123</code>.</p>
+</main>"#;
+    let prompt = Prompt::parse(html, "https://adventofcode.com/2025/day/1");
+    assert_eq!(prompt.parts(), 1);
+
+    assert_eq!(
+        prompt.text(30, false),
+        "\
+--- Day 1: Test ---
+
+The elves need fifty stars,
+see your input[1].
+
+    1 2
+    3 4
+
+  - One item in a list that is
+    long enough to wrap.
+  - Two
+
+[1] https://adventofcode.com/2025/day/1/input
+"
+    );
+    assert!(prompt.text(80, true).contains("\x1b[1mfifty\x1b[0m"));
+
+    assert_eq!(
+        prompt.markdown(),
+        "\
+## --- Day 1: Test ---
+
+The elves need **fifty** stars, see [your input](https://adventofcode.com/2025/day/1/input).
+
+```
+1 2
+3 4
+```
+
+- One `item` in a list that is long enough to wrap.
+- Two
+"
+    );
+}
@@ -1,44 +1,110 @@
-use chrono::{FixedOffset, NaiveDate, TimeDelta, Utc};
-use clap::{ArgAction, Command, Parser, ValueEnum};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use chrono_tz::America::New_York;
+use clap::{ArgAction, ArgMatches, Command, Parser, ValueEnum};
 use clap_complete::Shell;
 use notify::{RecommendedWatcher, Watcher};
-use regex::bytes::Regex;
-use solver_interface::{BenchResult, ParentSolver};
-use ureq::http::Response;
-use ureq::{Agent, Body};
+use solver_interface::{
+    AllocStats, BenchResult, Limits, Panic, ParentSolver, PartStats, SolverError,
+};
 
 use std::borrow::Cow;
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
 use std::fmt::Display;
-use std::fs::{File, create_dir_all};
-use std::io::{BufWriter, Write, stderr, stdin, stdout};
-use std::iter::Sum;
-use std::ops::Div;
-use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::fs::create_dir_all;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{IsTerminal, Write, stderr, stdin, stdout};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, SyncSender, channel, sync_channel};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
+use crate::build::{Build, build_days};
+use crate::config::{Config, DayConfig, with_negations};
+use crate::examples::{Example, code_blocks, find_examples, merge_answers};
+use crate::history::{HistoryEntry, find_baseline, git_commit};
+use crate::http::{Client, HttpOptions, Page, Retry};
+use crate::jsonl::{append_json_line, read_json_lines};
+use crate::leaderboard::{LEADERBOARD_REFRESH_SECS, Leaderboard};
+use crate::output::{OutputFormat, Record, RecordWriter};
+use crate::personal::{PersonalStats, replace_block};
+use crate::render::Prompt;
+use crate::scaffold::{register_member, scaffold_day};
+use crate::session::{Session, is_logged_out, logged_in_user};
+use crate::stats::{CONFIDENCE, Stats};
+use crate::submit::{Attempt, Outcome, check_attempt, next_part, post_answer};
+use crate::test_inputs::{TestResult, TestRow, Tests, parse_tests, print_test_table, saved_tests};
 use crate::{AocError, Res};
 
-/// User agent (see [Eric's post on the
-/// subreddit](https://www.reddit.com/r/adventofcode/comments/z9dhtd))
-const USER_AGENT: &str = "github.com/drewtato/aoc2025";
-
 /// How long to wait between polls in watch mode
 const WATCH_POLL_TIME: Duration = Duration::from_millis(20);
 
 const YEAR: u32 = 2025;
 
-/// Settings for running AoC. Usually created with [`clap::Parser::parse`].
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Pages fetched from the site, kept so they're only sent again when they
+/// change. Outside `inputs` so caching doesn't wake up watch mode. Each
+/// account other than the default one gets a directory inside.
+const HTTP_CACHE_DIR: &str = "./.cache/http";
+
+/// Benchmark results of each day and part, by year. Outside `inputs` so
+/// benchmarking in watch mode doesn't start another run.
+const BENCH_HISTORY_DIR: &str = "./.cache/bench";
+
+/// Private leaderboards, saved by year and id.
+const LEADERBOARD_CACHE_DIR: &str = "./.cache/leaderboard";
+
+/// Columns the prompt is wrapped to in read mode when `$COLUMNS` isn't set.
+const PROMPT_WIDTH: usize = 80;
+
+/// How many times wait mode asks for an input the site says isn't out yet.
+const RELEASE_RETRIES: u32 = 30;
+
+/// Least time between those requests. Up to as much again is added at random,
+/// so clocks that agree don't all ask at once.
+const RELEASE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Where stats mode writes the personal stats table.
+const README: &str = "README.md";
+
+const CONFIG_HELP: &str = "\
+Defaults for every option can be set in aoc.toml, in the project directory or \
+in ~/.config/aoc. Keys are the long option names, like `bench-time = 500`. \
+Tables like [days.10] override release, debug, timeout, and memory-limit for \
+one day in every year, [days.\"2024/10\"] for one year only, and \
+[days.9.parts.2] overrides the timeout for one part. Options on \
+the command line win over both, and flags turned on there can be turned off \
+with `--no-<flag>`, like `--no-release`.";
+
+/// Selected days, each with the parts to run. No parts means the usual ones.
+type DayParts = Vec<(u32, Vec<u32>)>;
+
+/// Stands in for the part number when reporting parse times.
+const PARSE_PART: u32 = 0;
+
+/// Settings for running AoC. Usually created with [`Settings::from_args`].
 #[derive(Debug, Parser)]
-#[command(about = "A runner for Advent of Code", version = clap::crate_version!())]
+#[command(
+    about = "A runner for Advent of Code",
+    version = clap::crate_version!(),
+    after_long_help = CONFIG_HELP
+)]
 pub struct Settings {
     /// Specify which days to run.
     ///
-    /// Passing 0 will run all 12. To run a specific part, pass `day.part`, like
-    /// `2.1` for part 1 of day 2, or `2.1.2` for both parts of day 2 (same as
-    /// `2`).
+    /// Passing 0 will run every day of the year: 12 from 2025 on, and 25
+    /// before. To run a specific part, pass `day.part`, like `2.1` for part 1
+    /// of day 2, or `2.1.2` for both parts of day 2 (same as `2`).
+    ///
+    /// A range like `3-7` runs several days, and `!9` leaves a day out, on its
+    /// own meaning every other day. `last` is the latest released day, and
+    /// `today` is the one released today or within the hour. Days from another
+    /// year than `--year` start with the year, like `2024/5.1`.
     pub days: Vec<String>,
 
     /// Select which mode to run in.
@@ -61,6 +127,27 @@ pub struct Settings {
     #[arg(short = 'c', long = "bench-count", default_value_t = 0)]
     pub bench_count: u32,
 
+    /// Label to save benchmark results under in the bench history.
+    ///
+    /// Defaults to the current git commit.
+    #[arg(long)]
+    pub bench_label: Option<String>,
+
+    /// Compare benchmark results to the latest results with this label.
+    ///
+    /// By default, results are compared to the previous run.
+    #[arg(long)]
+    pub baseline: Option<String>,
+
+    /// Exit with an error when a benchmark is significantly slower than its
+    /// baseline.
+    #[arg(long)]
+    pub fail_on_regression: bool,
+
+    /// Number of untimed iterations to run before measuring in bench mode.
+    #[arg(long, default_value_t = 3)]
+    pub warmup: u32,
+
     /// Hide answers in output.
     #[arg(short = 'a', long)]
     pub hide_answers: bool,
@@ -69,9 +156,21 @@ pub struct Settings {
     #[arg(short, long)]
     pub exit_on_incorrect: bool,
 
-    // /// Runs days in parallel.
-    // #[arg(long, short)]
-    // pub parallel: bool,
+    /// Runs days in parallel.
+    ///
+    /// Starts up to `--jobs` solvers at once in run, save, and validate modes.
+    /// Results are still printed in the order the days were given. Ignored in
+    /// bench mode, since concurrent solvers would skew the timings. Not
+    /// supported in submit mode or when running several test inputs.
+    #[arg(long, short)]
+    pub parallel: bool,
+
+    /// Number of solvers to run at once with `--parallel`.
+    ///
+    /// Defaults to the available parallelism.
+    #[arg(short, long)]
+    pub jobs: Option<NonZeroUsize>,
+
     /// Enables debug mode for the days.
     ///
     /// Pass this flag multiple times to enable more debug info.
@@ -80,9 +179,72 @@ pub struct Settings {
 
     /// Run with the specified test input.
     ///
-    /// Best used with one day selected. 0 corresponds to the real input.
-    #[arg(short, long, default_value_t = 0)]
-    pub test: u8,
+    /// 0 corresponds to the real input. `all` runs every saved test input,
+    /// and a list like `1,3-5` runs those. With more than one, run and validate
+    /// modes print a table comparing each answer to its saved answer, and save
+    /// and bench modes go through the inputs one by one.
+    #[arg(short, long = "test", default_value = "0", value_parser = parse_tests)]
+    pub tests: Tests,
+
+    /// Stop a solver if parsing or a part takes longer than this many
+    /// milliseconds.
+    ///
+    /// In bench mode, each iteration gets this much time.
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// Stop a solver if it uses more than this many mebibytes of memory.
+    ///
+    /// Only supported on Unix, where this limits the solver's address space.
+    #[arg(long)]
+    pub memory_limit: Option<u64>,
+
+    /// Report peak heap usage, allocation count, and bytes allocated for each
+    /// part.
+    ///
+    /// Builds solvers with a counting allocator, which makes them a little
+    /// slower, so benchmarks with it aren't added to the bench history.
+    #[arg(long)]
+    pub alloc_stats: bool,
+
+    /// Directory that `new` mode copies to create a day.
+    ///
+    /// Every `{{day}}` in the files and their names is replaced with the
+    /// two-digit day, `{{package}}` with the package name, like `day05` or
+    /// `y2024-day05`, and `{{crate}}` with the crate name, like `y2024_day05`.
+    #[arg(long, default_value = "template")]
+    pub template: PathBuf,
+
+    /// Also fetch the input and prompt when creating a day with `new` mode.
+    #[arg(long)]
+    pub fetch: bool,
+
+    /// In read mode, write the prompt to `prompt.md` next to the input instead
+    /// of printing it.
+    #[arg(long)]
+    pub markdown: bool,
+
+    /// Id of the private leaderboard shown by leaderboard mode.
+    ///
+    /// It's the number at the end of the leaderboard's URL. Usually set in
+    /// `aoc.toml`.
+    #[arg(long)]
+    pub leaderboard: Option<u64>,
+
+    /// In stats mode, add each part's latest benchmark median on the real
+    /// input to the table.
+    #[arg(long)]
+    pub bench_stats: bool,
+
+    /// Run solvers inside the runner instead of starting each one with cargo.
+    ///
+    /// The runner has to be built with `--features in-process`, and solvers
+    /// use the runner's profile instead of `--release`. Day 10 also needs
+    /// `--features in-process-day10`, which builds z3. Timeouts and memory
+    /// limits don't apply, and watch mode won't pick up changes. Only days
+    /// listed in `src/days.rs` can run this way.
+    #[arg(long)]
+    pub in_process: bool,
 
     /// Enables debug info for the runner.
     #[arg(short, long, action = ArgAction::Count)]
@@ -105,9 +267,7 @@ pub struct Settings {
     pub watch: bool,
 
     #[arg(skip = OnceLock::new())]
-    client: OnceLock<Agent>,
-    #[arg(skip = OnceLock::new())]
-    regex: OnceLock<Regex>,
+    client: OnceLock<Client>,
     #[arg(skip = OnceLock::new())]
     input_channel: OnceLock<(Receiver<InputMessage>, JoinHandle<()>)>,
     #[arg(skip = OnceLock::new())]
@@ -117,9 +277,75 @@ pub struct Settings {
     #[arg(long)]
     pub completions: Option<Shell>,
 
-    /// Override the year.
+    /// Year for days that don't give one.
+    ///
+    /// Solvers for 2025 are in `days/dayNN` and their inputs in
+    /// `inputs/dayNN`. Other years' solvers are in packages like
+    /// `days/y2024-day05`, and their inputs in `inputs/2024/day05`.
     #[arg(long, default_value_t = YEAR)]
     pub year: u32,
+
+    /// Site to fetch inputs and prompts from and submit answers to.
+    #[arg(long, default_value = BASE_URL)]
+    pub base_url: String,
+
+    /// Give up on a request to the site after this many seconds. 0 waits
+    /// forever.
+    #[arg(long, default_value_t = 30)]
+    pub http_timeout: u64,
+
+    /// Number of times to retry a fetch that timed out, couldn't connect, or
+    /// got a server error.
+    #[arg(long, default_value_t = 2)]
+    pub retries: u32,
+
+    /// Milliseconds to wait before the first retry. Doubles after each retry.
+    #[arg(long, default_value_t = 1000)]
+    pub retry_delay: u64,
+
+    /// Least milliseconds between the start of any two requests to the site.
+    #[arg(long, default_value_t = 1000)]
+    pub request_interval: u64,
+
+    /// Never connect to the site. Inputs and prompts that were fetched before
+    /// are used from the cache in `.cache/http`, and anything else is an error.
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Use the session token and inputs of a named account.
+    ///
+    /// The token is read from `$AOC_SESSION_<ACCOUNT>`, `./API_KEY.<account>`,
+    /// or `~/.config/aoc/session.<account>` instead of the default locations,
+    /// and inputs, answers, and submissions are kept in `inputs/<account>`.
+    #[arg(long, value_parser = parse_account)]
+    pub account: Option<String>,
+
+    /// Proxy for requests to the site, like `http://localhost:3128`.
+    ///
+    /// Defaults to the `HTTPS_PROXY`, `HTTP_PROXY`, and `ALL_PROXY` environment
+    /// variables.
+    #[arg(long)]
+    pub proxy: Option<String>,
+
+    /// Also print results to stdout in a machine-readable format.
+    ///
+    /// The human-readable text is still printed to stderr.
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output_format: OutputFormat,
+    #[arg(skip)]
+    record_writer: RecordWriter,
+    /// Solvers built by [`Settings::prebuild`], by day.
+    #[arg(skip)]
+    binaries: BTreeMap<u32, PathBuf>,
+    /// Per-day settings from `aoc.toml`.
+    #[arg(skip)]
+    day_configs: BTreeMap<(u32, u32), DayConfig>,
+    /// Flags given on the command line, which win over per-day settings.
+    #[arg(skip)]
+    from_cli: BTreeSet<String>,
+    /// The input being run, where 0 is the real input.
+    #[arg(skip)]
+    test: u8,
 }
 
 /// Mode to run [`Settings`] in.
@@ -131,8 +357,8 @@ pub enum Mode {
     Run,
     /// Benchmark the specified days.
     ///
-    /// This ignores part info and will always run initialization, part one, and
-    /// part two as fast as possible.
+    /// When no parts are given, this benchmarks parsing (for solvers that parse
+    /// separately), part one, and part two.
     #[value(alias("b"))]
     Bench,
     /// Save the specified days' output as validation files, to be used with
@@ -143,9 +369,75 @@ pub enum Mode {
     /// in validation files.
     #[value(alias("v"))]
     Validate,
-    /// Retrieve the prompt and test cases
+    /// Retrieve the prompt and save its examples as test cases, with the
+    /// answers it gives and an `examples.json` listing them
     #[value(alias("p"))]
     Prompt,
+    /// Show the saved prompt as text wrapped to the terminal, or write it as
+    /// Markdown with `--markdown`.
+    ///
+    /// The prompt is fetched if it hasn't been, or fetched again if it only
+    /// has part 1, in case part 1 has since been solved.
+    Read,
+    /// Run the specified days on the real input and submit the answers.
+    ///
+    /// When no part is given, submits the first part that hasn't been solved.
+    /// Answers that earlier attempts show are wrong are never submitted.
+    Submit,
+    /// Create the specified days from the template directory.
+    ///
+    /// Days that already exist are never overwritten. New days only run out of
+    /// process until they're added to `src/days.rs` and the `in-process`
+    /// feature.
+    #[value(alias("n"))]
+    New,
+    /// Show the private leaderboard set with `--leaderboard`.
+    ///
+    /// Lists members by score with their stars, then each selected day's star
+    /// times, or the latest day's when none are selected. With
+    /// `--output-format json`, the leaderboard is printed as the site sent it.
+    /// It's fetched at most once every 15 minutes.
+    #[value(alias("l"))]
+    Leaderboard,
+    /// Fetch your personal times and rewrite the personal stats table in
+    /// README.md.
+    ///
+    /// The table goes between the `<!-- personal-stats:start -->` and
+    /// `<!-- personal-stats:end -->` lines. With `--bench-stats`, each part's
+    /// latest benchmark median is added.
+    Stats,
+    /// Count down to the selected day's release, or the next day's when none
+    /// is selected, then fetch its input and prompt, create it from the
+    /// template if needed, and run it with `--watch`.
+    ///
+    /// Fetching is retried while the site says the input isn't out yet.
+    #[value(alias("w"))]
+    Wait,
+    /// Check that the session token works and show who it belongs to.
+    ///
+    /// The token is read from `$AOC_SESSION`, `./API_KEY`, or
+    /// `~/.config/aoc/session`, whichever is found first.
+    Session,
+}
+
+impl Mode {
+    /// The name used for this mode on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Run => "run",
+            Mode::Bench => "bench",
+            Mode::Save => "save",
+            Mode::Validate => "validate",
+            Mode::Prompt => "prompt",
+            Mode::Read => "read",
+            Mode::Submit => "submit",
+            Mode::New => "new",
+            Mode::Leaderboard => "leaderboard",
+            Mode::Stats => "stats",
+            Mode::Wait => "wait",
+            Mode::Session => "session",
+        }
+    }
 }
 
 macro_rules! debug_println {
@@ -157,6 +449,32 @@ macro_rules! debug_println {
 }
 
 impl Settings {
+    /// Parses the command line on top of the defaults from `aoc.toml` files.
+    /// Exits on invalid flags or `--help`, like [`clap::Parser::parse`].
+    pub fn from_args() -> Res<Self> {
+        use clap::{CommandFactory, FromArgMatches};
+
+        let mut args: Vec<OsString> = std::env::args_os().collect();
+        let command = with_negations(Self::command());
+        let cli = command.clone().get_matches_from(&args);
+        let from_cli = cli_ids(&cli);
+
+        let config = Config::load(&command)?;
+        let config_args = config
+            .args
+            .iter()
+            .filter(|(id, _)| !from_cli.contains(*id))
+            .flat_map(|(_, args)| args)
+            .map(OsString::from);
+        args.splice(1..1, config_args);
+
+        let matches = command.get_matches_from(args);
+        let mut settings = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        settings.day_configs = config.days_in(settings.year);
+        settings.from_cli = from_cli;
+        Ok(settings)
+    }
+
     pub fn run(&mut self) -> Res<()> {
         if let Some(shell) = self.completions {
             use clap::CommandFactory;
@@ -172,20 +490,71 @@ impl Settings {
         debug_println!(self.runner_debug, 2, "{:?}", self);
         debug_println!(self.runner_debug, 1, "Starting runner");
 
-        let mut day_parts = Vec::new();
-        // Can't use collect because I need to flatten the Vec inside the Result
-        for item in self.days.iter().map(|word| parse_day(word)) {
-            day_parts.extend_from_slice(&item?);
+        if self.in_process && (self.timeout.is_some() || self.memory_limit.is_some()) {
+            eprintln!("Timeouts and memory limits are ignored with --in-process");
+        }
+        if self.in_process && self.alloc_stats {
+            eprintln!("Allocation stats are ignored with --in-process");
         }
 
-        solver_time += loop {
-            let res = match self.mode {
-                Mode::Run => self.run_days(&day_parts),
-                Mode::Bench => self.benchmark(&day_parts),
-                Mode::Save => self.save(&day_parts),
-                Mode::Validate => self.validate(&day_parts),
-                Mode::Prompt => self.prompt(&day_parts),
+        // Days grouped by year, in the order each year was first given
+        let mut years: Vec<(u32, DayParts)> = Vec::new();
+        let mut excluded = BTreeSet::new();
+        for word in &self.days {
+            let (year, day_parts) = match parse_day(word, self.year)? {
+                (year, Selection::Days(day_parts)) => (year, day_parts),
+                (year, Selection::Exclude(days)) => {
+                    excluded.extend(days.into_iter().map(|day| (year, day)));
+                    (year, Vec::new())
+                }
             };
+            match years.iter_mut().find(|(y, _)| *y == year) {
+                Some((_, days)) => days.extend(day_parts),
+                None => years.push((year, day_parts)),
+            }
+        }
+        for (year, day_parts) in &mut years {
+            let year = *year;
+            // Only leaving days out means running the rest of the year
+            if day_parts.is_empty() && excluded.iter().any(|&(y, _)| y == year) {
+                *day_parts = (1..=days_in_year(year)).map(|day| (day, vec![])).collect();
+            }
+            day_parts.retain(|&(day, _)| {
+                if excluded.contains(&(year, day)) {
+                    return false;
+                }
+                let found = (1..=days_in_year(year)).contains(&day);
+                if !found {
+                    eprintln!("Day {day} not found, skipping");
+                }
+                found
+            });
+        }
+        if years.is_empty() {
+            years.push((self.year, Vec::new()));
+        }
+        if self.mode == Mode::Wait {
+            for (year, day_parts) in &mut years {
+                if day_parts.is_empty() {
+                    day_parts.push((next_release(*year, Utc::now())?, Vec::new()));
+                }
+            }
+        }
+        if self.in_process
+            && let Some(&(year, _)) = years.iter().find(|&&(year, _)| year != YEAR)
+        {
+            return Err(AocError::InProcessYear(year));
+        }
+
+        solver_time += loop {
+            let res = self.run_years(&years);
+
+            // Once the puzzle is out, wait mode carries on like `--watch`
+            if self.mode == Mode::Wait && res.is_ok() {
+                self.mode = Mode::Run;
+                self.watch = true;
+                continue;
+            }
 
             if !self.watch {
                 break res?;
@@ -216,6 +585,124 @@ impl Settings {
         Ok(())
     }
 
+    /// Runs the mode for each year's days in turn.
+    fn run_years(&mut self, years: &[(u32, DayParts)]) -> Res<Duration> {
+        let mut time = Duration::ZERO;
+        let mut build_failures = 0;
+        self.test = match self.tests {
+            Tests::One(test) => test,
+            _ => 0,
+        };
+        if self.parallel {
+            match self.mode {
+                Mode::Submit => return Err(AocError::ParallelUnsupported("in submit mode")),
+                Mode::Run | Mode::Validate if self.tests.is_many() => {
+                    return Err(AocError::ParallelUnsupported("with several test inputs"));
+                }
+                _ => (),
+            }
+        }
+        for (year, day_parts) in years {
+            self.year = *year;
+            if years.len() > 1 {
+                eprintln!("Year {year}");
+            }
+            let (day_parts, failures) = self.prebuild(day_parts)?;
+            build_failures += failures;
+            let day_parts = &day_parts[..];
+            let many_tests = self.tests.is_many();
+            time += match self.mode {
+                Mode::Run | Mode::Validate if many_tests => self.run_tests(day_parts),
+                Mode::Save | Mode::Bench if many_tests => self.each_test(day_parts),
+                Mode::Run => self.run_days(day_parts),
+                Mode::Bench => self.benchmark(day_parts),
+                Mode::Save => self.save(day_parts),
+                Mode::Validate => self.validate(day_parts),
+                Mode::Prompt => self.prompt(day_parts),
+                Mode::Read => self.read(day_parts),
+                Mode::Submit => self.submit(day_parts),
+                Mode::New => self.new_days(day_parts),
+                Mode::Leaderboard => self.leaderboard(day_parts),
+                Mode::Stats => self.personal_stats(),
+                Mode::Wait => self.wait(day_parts),
+                Mode::Session => self.check_session(),
+            }?;
+        }
+        if build_failures > 0 {
+            return Err(AocError::BuildFailed(build_failures));
+        }
+        Ok(time)
+    }
+
+    /// Builds every selected day once, so solvers can be started without
+    /// waiting on cargo. Compiler errors are printed grouped by crate.
+    ///
+    /// Returns the days that can run, leaving out the ones that didn't build,
+    /// and how many crates failed.
+    fn prebuild(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<(DayParts, usize)> {
+        self.binaries.clear();
+        if self.in_process
+            || matches!(
+                self.mode,
+                Mode::Prompt
+                    | Mode::Read
+                    | Mode::New
+                    | Mode::Leaderboard
+                    | Mode::Stats
+                    | Mode::Wait
+                    | Mode::Session
+            )
+            || day_parts.is_empty()
+        {
+            return Ok((day_parts.to_vec(), 0));
+        }
+
+        // Packages built with each profile. Benches always use the release
+        // profile
+        let mut profiles: BTreeMap<bool, BTreeSet<String>> = BTreeMap::new();
+        for &(day, _) in day_parts {
+            let release = self.mode == Mode::Bench || self.day_release(day);
+            profiles
+                .entry(release)
+                .or_default()
+                .insert(self.package_name(day));
+        }
+
+        let mut build = Build::default();
+        for (release, packages) in profiles {
+            debug_println!(self.runner_debug, 1, "Building {} days", packages.len());
+            build.extend(build_days(packages, release, self.alloc_stats)?);
+        }
+
+        for (krate, warnings) in &build.warnings {
+            eprintln!("{krate}: {warnings} warnings");
+        }
+        for (krate, errors) in &build.errors {
+            eprintln!("{krate}: {} errors", errors.len());
+            for error in errors {
+                eprintln!("{error}");
+            }
+        }
+        let failures = build.errors.len();
+        self.binaries = build.binaries;
+        if failures == 0 {
+            return Ok((day_parts.to_vec(), 0));
+        }
+
+        let built = day_parts
+            .iter()
+            .filter(|&&(day, _)| {
+                let built = self.binaries.contains_key(&day);
+                if !built {
+                    eprintln!("Skipping day {day}, which didn't build");
+                }
+                built
+            })
+            .cloned()
+            .collect();
+        Ok((built, failures))
+    }
+
     /// Returns `true` when asked to exit
     fn watch(&mut self) -> Res<bool> {
         // OnceLock guarantees only one function will be executed, so only one thread
@@ -296,47 +783,47 @@ impl Settings {
     fn run_days(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
         let mut test_time = Duration::ZERO;
         let mut buffer = String::new();
+        let mut parallel = self.start_parallel(day_parts)?;
+        let mut panics = 0;
+
         for &(day, ref parts) in day_parts {
             debug_println!(self.runner_debug, 1, "Starting day {day}");
 
             let mut day_time = Duration::ZERO;
 
-            if !(1..=12).contains(&day) {
-                eprintln!("Day {day} not found, skipping");
-                continue;
-            }
-
-            let file = self.get_input(day)?;
-            let mut solver = self.day_to_solver(day, file)?;
+            let mut solver = self.day_solver(day, parallel.as_mut())?;
 
-            if parts.is_empty() {
-                let time = solver.part_one(&mut buffer)?;
-                day_time += time;
-
-                if !self.hide_answers {
-                    print_times(day, 1, &buffer, time);
-                } else {
-                    print_times(day, 1, "", time);
+            let parse_time = match solver.parse() {
+                Err(e) if e.is_panic() => {
+                    // None of the parts can run without the parsed input
+                    eprintln!("d{day:02} parse: {e}\n");
+                    panics += 1;
+                    continue;
                 }
-                buffer.clear();
-
-                let time = solver.part_two(&mut buffer)?;
+                result => result?,
+            };
+            if let Some(time) = parse_time {
                 day_time += time;
-
-                if !self.hide_answers {
-                    print_times(day, 2, &buffer, time);
-                } else {
-                    print_times(day, 2, "", time);
-                }
-                buffer.clear();
+                eprintln!("d{day:02} parse: ({time:?})");
+                self.write_record(Record {
+                    day,
+                    part: PARSE_PART,
+                    duration_ns: Some(time.as_nanos() as u64),
+                    ..Default::default()
+                })?;
             }
 
+            let parts: &[u32] = if parts.is_empty() { &[1, 2] } else { parts };
+
             for &part in parts {
-                let time = match part {
-                    1 => solver.part_one(&mut buffer),
-                    2 => solver.part_two(&mut buffer),
-                    p => solver.run_any(p, &mut buffer),
-                }?;
+                let PartStats { time, alloc } = match solver.run_part(part, &mut buffer) {
+                    Err(e) if e.is_panic() => {
+                        eprintln!("d{day:02}p{part:02}: {e}");
+                        panics += 1;
+                        continue;
+                    }
+                    result => result?,
+                };
                 day_time += time;
 
                 if !self.hide_answers {
@@ -344,6 +831,16 @@ impl Settings {
                 } else {
                     print_times(day, part, "", time);
                 }
+                if let Some(alloc) = &alloc {
+                    print_alloc(alloc);
+                }
+                self.write_record(Record {
+                    day,
+                    part,
+                    answer: self.shown_answer(&buffer),
+                    duration_ns: Some(time.as_nanos() as u64),
+                    ..Record::with_alloc(alloc)
+                })?;
                 buffer.clear();
             }
 
@@ -351,11 +848,140 @@ impl Settings {
             test_time += day_time;
         }
         eprintln!("All: {test_time:?}");
+        if panics > 0 {
+            return Err(AocError::Panicked(panics));
+        }
         Ok(test_time)
     }
 
+    /// Runs each day's parts on every selected test input and prints a table
+    /// comparing them to the saved answers.
+    fn run_tests(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        let mut time = Duration::ZERO;
+        let mut rows = Vec::new();
+        let mut buffer = String::new();
+
+        for &(day, ref parts) in day_parts {
+            let tests = self.selected_tests(day)?;
+            if tests.is_empty() {
+                eprintln!("d{day:02}: no test inputs");
+                continue;
+            }
+            let parts: &[u32] = if parts.is_empty() { &[1, 2] } else { parts };
+
+            for test in tests {
+                self.test = test;
+                let answers = match std::fs::read_to_string(self.answer_file_name(day, test)) {
+                    Ok(answers) => answers,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                    Err(e) => return Err(e.into()),
+                };
+                let answers: Vec<&str> = answers.lines().collect();
+
+                let mut solver = self.day_solver(day, None)?;
+                for &part in parts {
+                    let (stats, result) = match solver.run_part(part, &mut buffer) {
+                        Err(e) if e.is_panic() => (None, TestResult::Panicked(e.to_string())),
+                        result => {
+                            let expected = answers
+                                .get(part as usize - 1)
+                                .copied()
+                                .filter(|a| !a.is_empty());
+                            (Some(result?), TestResult::new(&buffer, expected))
+                        }
+                    };
+                    let part_time = stats.map_or(Duration::ZERO, |s| s.time);
+                    time += part_time;
+
+                    self.write_record(Record {
+                        day,
+                        part,
+                        answer: self.shown_answer(&buffer).filter(|_| stats.is_some()),
+                        duration_ns: stats.map(|s| s.time.as_nanos() as u64),
+                        correct: match &result {
+                            TestResult::Correct => Some(true),
+                            TestResult::Wrong(_) => Some(false),
+                            _ => None,
+                        },
+                        ..Record::with_alloc(stats.and_then(|s| s.alloc))
+                    })?;
+                    rows.push(TestRow {
+                        day,
+                        test,
+                        part,
+                        time: part_time,
+                        answer: std::mem::take(&mut buffer),
+                        result,
+                    });
+                }
+            }
+        }
+        self.test = 0;
+
+        if rows.is_empty() {
+            return Err(AocError::NoTestInputs);
+        }
+        print_test_table(&rows, self.hide_answers);
+        let failed = |r: &&TestRow| matches!(r.result, TestResult::Wrong(_));
+        let panicked = |r: &&TestRow| matches!(r.result, TestResult::Panicked(_));
+        let wrong = rows.iter().filter(failed).count() as u32;
+        let panics = rows.iter().filter(panicked).count() as u32;
+        if panics > 0 {
+            return Err(AocError::Panicked(panics));
+        }
+        if wrong > 0 {
+            return Err(AocError::MultipleIncorrect(wrong));
+        }
+        eprintln!("All: {time:?}");
+        Ok(time)
+    }
+
+    /// Runs save or bench mode once for each selected test input of each day.
+    fn each_test(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        let mut time = Duration::ZERO;
+        for &(day, ref parts) in day_parts {
+            let tests = self.selected_tests(day)?;
+            if tests.is_empty() {
+                eprintln!("d{day:02}: no test inputs");
+            }
+            for test in tests {
+                self.test = test;
+                eprintln!("d{day:02} test {test:02}");
+                let day_parts = [(day, parts.clone())];
+                time += if self.mode == Mode::Save {
+                    self.save(&day_parts)
+                } else {
+                    self.benchmark(&day_parts)
+                }?;
+            }
+        }
+        self.test = 0;
+        Ok(time)
+    }
+
+    /// The test inputs `--test` picks out for a day. Listed tests that don't
+    /// exist are skipped.
+    fn selected_tests(&self, day: u32) -> Res<Vec<u8>> {
+        let saved = saved_tests(&self.input_base_name(day))?;
+        Ok(match &self.tests {
+            Tests::One(test) => vec![*test],
+            Tests::All => saved,
+            Tests::List(tests) => tests
+                .iter()
+                .copied()
+                .filter(|test| {
+                    let found = saved.contains(test);
+                    if !found {
+                        eprintln!("d{day:02}: test {test:02} not found, skipping");
+                    }
+                    found
+                })
+                .collect(),
+        })
+    }
+
     fn get_input(&mut self, day: u32) -> Res<Vec<u8>> {
-        let input_main = input_file_name(day, 0);
+        let input_main = self.input_file_name(day, 0);
         if !input_main.exists() {
             let time_until_release = time_until_input_is_released(day, self.year);
             // If the puzzle is very far out
@@ -368,7 +994,7 @@ impl Settings {
             }
 
             // If the puzzle hasn't been out for at least 5 seconds
-            if time_until_release > TimeDelta::seconds(-5) {
+            if time_until_release > TimeDelta::seconds(-5) && !self.offline {
                 use std::io::Write;
                 let delay = time_until_release + TimeDelta::seconds(5);
 
@@ -392,7 +1018,7 @@ impl Settings {
         let input = if self.test == 0 {
             std::fs::read(input_main)
         } else {
-            std::fs::read(input_file_name(day, self.test))
+            std::fs::read(self.input_file_name(day, self.test))
         }?;
 
         Ok(input)
@@ -401,184 +1027,369 @@ impl Settings {
     /// Get the input from the network and write it to the filesystem. Will
     /// overwrite any existing input files.
     fn get_input_network(&mut self, day: u32) -> Res<()> {
-        let api_key = api_key()?;
-        let api_key = api_key.trim();
-
         // Get main input
-        let url = format!("https://adventofcode.com/{}/day/{day}/input", self.year);
+        let url = format!("{}/input", self.day_url(day));
         eprintln!("Fetching {url}");
 
-        let req = self
-            .client()
-            .get(&url)
-            .header("cookie", &format!("session={api_key}"))
-            .call()?;
-        if !req.status().is_success() {
-            return Err(AocError::input_response(
-                req.status(),
-                req.into_body().read_to_string()?,
-            ));
+        let page = self.get_page(&url)?;
+        if !page.status.is_success() {
+            return Err(AocError::input_response(page.status, page.text()));
         }
-        let data = read_to_vec(req)?;
+        let data = page.body;
 
-        let path = input_base_name(day);
+        let path = self.input_base_name(day);
         create_dir_all(path)?;
-        let input_path = input_file_name(day, 0);
+        let input_path = self.input_file_name(day, 0);
         std::fs::write(input_path, data)?;
 
-        self.get_prompt(day, api_key)?;
+        self.get_prompt(day)?;
 
         Ok(())
     }
 
-    fn client(&self) -> &Agent {
-        self.client.get_or_init(|| {
-            Agent::config_builder()
-                .user_agent(USER_AGENT)
-                .build()
-                .new_agent()
-        })
+    /// Waits for each day to release, fetches it, and creates it if it
+    /// doesn't exist.
+    fn wait(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        for &(day, _) in day_parts {
+            if !self.input_file_name(day, 0).exists() {
+                let live = stderr().is_terminal();
+                let release = release_time(day, self.year);
+                countdown(day, release, &mut SystemClock, &mut stderr(), live)?;
+                self.fetch_when_released(day)?;
+            }
+
+            let dir = self.day_dir(day);
+            if !dir.exists() {
+                scaffold_day(&self.template, &dir, day, &self.package_name(day))?;
+                eprintln!("Created {}", dir.display());
+                if register_member(Path::new("Cargo.toml"), &dir.to_string_lossy())? {
+                    eprintln!("Added {} to the workspace", dir.display());
+                }
+            }
+        }
+        Ok(Duration::ZERO)
     }
 
-    fn get_prompt(&mut self, day: u32, api_key: &str) -> Result<(), AocError> {
-        let url = format!("https://adventofcode.com/{}/day/{day}", self.year);
-        if self.runner_debug > 0 {
-            eprintln!("Fetching {url}");
+    /// Fetches the input and prompt, trying again while the site doesn't have
+    /// them yet. Clocks never quite agree, so the first try can be early.
+    fn fetch_when_released(&mut self, day: u32) -> Res<()> {
+        let mut attempt = 0;
+        loop {
+            match self.get_input_network(day) {
+                Err(AocError::InputResponse { status, .. })
+                    if status == 404 && attempt < RELEASE_RETRIES =>
+                {
+                    attempt += 1;
+                    let delay = RELEASE_RETRY_DELAY + jitter(RELEASE_RETRY_DELAY);
+                    eprintln!(
+                        "Day {day} isn't out yet, trying again in {delay:.1?} \
+                         ({attempt}/{RELEASE_RETRIES})"
+                    );
+                    std::thread::sleep(delay);
+                }
+                result => return result,
+            }
         }
-        let req = self
-            .client()
-            .get(&url)
-            .header("cookie", &format!("session={api_key}"))
-            .call()?;
-        if !req.status().is_success() {
-            return Err(AocError::prompt_response(
-                req.status(),
-                req.into_body().read_to_string()?,
-            ));
-        }
-        let text = read_to_vec(req)?;
-        let prompt_path = prompt(day);
-        std::fs::write(prompt_path, &text)?;
+    }
 
-        // Save each code block as a test case
-        let regex = self.regex();
+    /// Gets a page through the HTTP cache. The session token is only looked
+    /// up if the site has to be asked, so offline runs work without one.
+    fn get_page(&self, url: &str) -> Res<Page> {
+        let mut session = None;
+        let page = self.client()?.get_cached(url, || {
+            let session = session.insert(self.session()?);
+            Ok(session.token.clone())
+        })?;
+        if let Some(session) = session
+            && is_logged_out(page.status, &page.text())
+        {
+            return Err(session.expired());
+        }
+        Ok(page)
+    }
 
-        for (i, code) in regex.captures_iter(&text).enumerate() {
-            let Ok(i) = (i + 1).try_into() else {
-                eprintln!("{}, skipping the rest", AocError::TooManyTestCases);
-                break;
-            };
+    fn session(&self) -> Res<Session> {
+        let session = Session::find(self.account.as_deref())?;
+        debug_println!(
+            self.runner_debug,
+            1,
+            "Using the session token from {}",
+            session.source
+        );
+        Ok(session)
+    }
 
-            debug_println!(self.runner_debug, 1, "Got a code match, making a test {i}");
+    fn base_url(&self) -> &str {
+        self.base_url.trim_end_matches('/')
+    }
+
+    /// The puzzle page of a day.
+    fn day_url(&self, day: u32) -> String {
+        format!("{}/{}/day/{day}", self.base_url(), self.year)
+    }
 
-            let code = &code[1];
+    fn http_options(&self) -> HttpOptions {
+        HttpOptions {
+            timeout: (self.http_timeout > 0).then(|| Duration::from_secs(self.http_timeout)),
+            proxy: self.proxy.clone(),
+            retry: Retry {
+                retries: self.retries,
+                delay: Duration::from_millis(self.retry_delay),
+            },
+            interval: Duration::from_millis(self.request_interval),
+            offline: self.offline,
+            cache_dir: Some(Path::new(HTTP_CACHE_DIR).join(self.account.as_deref().unwrap_or(""))),
+        }
+    }
 
-            let test_path = input_file_name(day, i);
-            let file = File::create(test_path)?;
-            let mut file = BufWriter::new(file);
+    fn client(&self) -> Res<&Client> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
+        }
+        let client = self.http_options().client()?;
+        Ok(self.client.get_or_init(|| client))
+    }
 
-            html_escape::decode_html_entities_to_writer(
-                std::str::from_utf8(code).map_err(|_| AocError::NonUtf8InPromptCodeBlock)?,
-                &mut file,
-            )?;
+    fn get_prompt(&mut self, day: u32) -> Result<(), AocError> {
+        let url = self.day_url(day);
+        if self.runner_debug > 0 {
+            eprintln!("Fetching {url}");
         }
+        let page = self.get_page(&url)?;
+        if !page.status.is_success() {
+            return Err(AocError::prompt_response(page.status, page.text()));
+        }
+        let text = page.body;
+        create_dir_all(self.input_base_name(day))?;
+        let prompt_path = self.prompt_file_name(day);
+        std::fs::write(prompt_path, &text)?;
 
+        let html = String::from_utf8(text).map_err(|_| AocError::NonUtf8Prompt)?;
+        self.save_examples(day, &html)
+    }
+
+    /// Saves the examples in a prompt as test inputs, with the answers it
+    /// gives, and lists them in the manifest.
+    fn save_examples(&self, day: u32, html: &str) -> Res<()> {
+        let examples = find_examples(html);
+        self.remove_stale_examples(day, html, &examples)?;
+        for example in &examples {
+            debug_println!(
+                self.runner_debug,
+                1,
+                "Found an example in part {}, making test {}",
+                example.part,
+                example.test
+            );
+            std::fs::write(self.input_file_name(day, example.test), &example.input)?;
+            if example.answers.iter().all(String::is_empty) {
+                continue;
+            }
+            let path = self.answer_file_name(day, example.test);
+            let saved = match std::fs::read_to_string(&path) {
+                Ok(saved) => saved,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            };
+            std::fs::write(path, merge_answers(&saved, &example.answers))?;
+        }
+        let manifest = serde_json::to_string_pretty(&examples)?;
+        std::fs::write(self.examples_file_name(day), manifest + "\n")?;
         Ok(())
     }
 
-    fn regex(&self) -> &Regex {
-        self.regex
-            .get_or_init(|| Regex::new(r"<pre>\s*<code>([^<]+)</code>\s*</pre>").unwrap())
+    /// Removes test inputs that an earlier fetch saved but that aren't
+    /// examples now: ones in the old manifest, or, from before there was a
+    /// manifest, ones that are a code block from the prompt. Other test inputs
+    /// were made by hand and are kept.
+    fn remove_stale_examples(&self, day: u32, html: &str, examples: &[Example]) -> Res<()> {
+        let old: Vec<Example> = match std::fs::read_to_string(self.examples_file_name(day)) {
+            Ok(manifest) => serde_json::from_str(&manifest).unwrap_or_default(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let blocks = code_blocks(html);
+        for test in saved_tests(&self.input_base_name(day))? {
+            if examples.iter().any(|e| e.test == test) {
+                continue;
+            }
+            let path = self.input_file_name(day, test);
+            let input = String::from_utf8(std::fs::read(&path)?).unwrap_or_default();
+            if old.iter().any(|e| e.test == test) || blocks.contains(&input) {
+                debug_println!(self.runner_debug, 1, "Removing stale test {test}");
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
     }
 
     fn benchmark(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
         let mut solver_time = Duration::ZERO;
+        let mut regressions = 0;
+        let commit = git_commit();
 
         for &(day, ref parts) in day_parts {
             debug_println!(self.runner_debug, 2, "starting bencher for day {day}");
             let input = self.get_input(day)?;
-            let mut bencher = self.day_to_bencher(day, input)?;
+            let (mut bencher, options) = self.day_to_bencher(day, input)?;
             debug_println!(self.runner_debug, 2, "ParentSolver started");
 
-            for &part in parts {
-                debug_println!(self.runner_debug, 1, "Benching part {part}");
-                let (mut times, answer) = if self.bench_count == 0 {
-                    debug_println!(self.runner_debug, 2, "this is a timed bench");
-                    let bench_time = Duration::from_millis(self.bench_time);
-
-                    let BenchResult { times, answer } = bencher.bench(part, 1)?;
-                    debug_println!(self.runner_debug, 2, "got {} results", times.len());
-
-                    if times[0] > bench_time {
-                        (times, answer.into_owned())
-                    } else {
-                        let first_answer = answer.into_owned();
-
-                        let test_times = if times[0] > bench_time / 10 {
-                            times
-                        } else {
-                            let BenchResult { mut times, answer } = bencher.bench(part, 10)?;
-                            if answer != first_answer {
-                                return Err(AocError::IncorrectAnswer);
-                            }
-                            times.sort_unstable();
-                            // Remove the largest times
-                            times.truncate(7);
-                            times
-                        };
+            let parts = if parts.is_empty() {
+                let mut parts = vec![1, 2];
+                if bencher.parse()?.is_some() {
+                    parts.insert(0, PARSE_PART);
+                }
+                parts
+            } else {
+                parts.clone()
+            };
 
-                        let avg_test_time = average(&test_times);
-                        let iters_to_do = bench_time.as_nanos() / avg_test_time.as_nanos();
+            for part in parts {
+                debug_println!(self.runner_debug, 1, "Benching part {part}");
+                bencher.set_timeout(options.timeout(part));
+                let BenchResult {
+                    times,
+                    answer,
+                    alloc,
+                } = self.bench_part(&mut bencher, part)?;
+                debug_println!(self.runner_debug, 2, "got {} results", times.len());
 
-                        let BenchResult { times, answer } =
-                            bencher.bench(part, iters_to_do as _)?;
-                        if answer != first_answer {
-                            return Err(AocError::IncorrectAnswer);
+                let stats = Stats::new(&times);
+                print_stats(day, part, &stats, &answer);
+                if let Some(alloc) = &alloc {
+                    print_alloc(alloc);
+                }
+                self.write_record(Record {
+                    day,
+                    part,
+                    answer: self.shown_answer(&answer).filter(|_| part != PARSE_PART),
+                    samples: Some(stats.samples),
+                    avg_ns: Some(stats.mean.as_nanos() as u64),
+                    median_ns: Some(stats.median.as_nanos() as u64),
+                    min_ns: Some(stats.min.as_nanos() as u64),
+                    max_ns: Some(stats.max.as_nanos() as u64),
+                    stddev_ns: Some(stats.stddev.as_nanos() as u64),
+                    mad_ns: Some(stats.mad.as_nanos() as u64),
+                    ci_low_ns: Some(stats.ci.0.as_nanos() as u64),
+                    ci_high_ns: Some(stats.ci.1.as_nanos() as u64),
+                    outliers: Some(stats.outliers.total()),
+                    ..Record::with_alloc(alloc)
+                })?;
+                solver_time += stats.mean;
+
+                // The counting allocator slows solvers down, so these timings
+                // can't be compared with the others
+                if self.alloc_stats {
+                    continue;
+                }
+                let history_path = self.bench_history_file_name(day, part);
+                let history: Vec<HistoryEntry> = read_json_lines(&history_path)?;
+                let entry = HistoryEntry::new(
+                    self.bench_label.as_deref(),
+                    commit.clone(),
+                    self.test,
+                    &stats,
+                );
+                match find_baseline(&history, self.test, self.baseline.as_deref()) {
+                    Some(baseline) => {
+                        let comparison = entry.compare(baseline);
+                        eprintln!(
+                            "        {:+.1}% vs {} ({})",
+                            comparison.change * 100.0,
+                            comparison.baseline,
+                            comparison.verdict()
+                        );
+                        if comparison.is_regression() {
+                            regressions += 1;
+                        }
+                    }
+                    None => {
+                        if let Some(baseline) = &self.baseline {
+                            eprintln!("        no results labeled {baseline:?} to compare to");
                         }
-                        (times, first_answer)
                     }
-                } else {
-                    debug_println!(self.runner_debug, 2, "this is a counted bench");
-                    let BenchResult { times, answer } = bencher.bench(part, self.bench_count)?;
-                    (times, answer.into_owned())
-                };
-
-                debug_println!(self.runner_debug, 2, "got {} results", times.len());
-
-                let mut to_remove = (times.len().ilog2() as usize).saturating_sub(1) * 2;
-                if times.len() > 1 {
-                    to_remove += 1;
                 }
-                let new_len = times.len() - to_remove;
-                times.truncate(new_len);
-
-                let avg = average(&times);
-                let median = times[times.len() / 2];
-                let stderr = stderr();
-                eprint!("d{day:02}p{part:02}: avg ");
-                readable_time(&stderr, avg, 3).unwrap();
-                eprint!(", med ");
-                readable_time(&stderr, median, 3).unwrap();
-                eprintln!(" ({answer:?})");
-                solver_time += avg;
+                if let Some(dir) = history_path.parent() {
+                    create_dir_all(dir)?;
+                }
+                append_json_line(&history_path, &entry)?;
             }
         }
 
+        if self.fail_on_regression && regressions > 0 {
+            return Err(AocError::Regressions(regressions));
+        }
+
         Ok(solver_time)
     }
 
+    /// Runs a part, or parsing for [`PARSE_PART`], as many times as fit in the
+    /// bench time, or `--bench-count` times.
+    fn bench_part(&self, bencher: &mut ParentSolver, part: u32) -> Res<BenchResult<'static>> {
+        let mut bench = |iters, warmup| {
+            if part == PARSE_PART {
+                bencher.bench_parse(iters, warmup)
+            } else {
+                bencher.bench(part, iters, warmup)
+            }
+        };
+
+        if self.bench_count != 0 {
+            debug_println!(self.runner_debug, 2, "this is a counted bench");
+            return Ok(bench(self.bench_count, self.warmup)?);
+        }
+
+        debug_println!(self.runner_debug, 2, "this is a timed bench");
+        let bench_time = Duration::from_millis(self.bench_time);
+
+        let first = bench(1, self.warmup)?;
+        debug_println!(self.runner_debug, 2, "got {} results", first.times.len());
+
+        if first.times[0] > bench_time {
+            return Ok(first);
+        }
+        let first_answer = first.answer;
+
+        let test_time = if first.times[0] > bench_time / 10 {
+            first.times[0]
+        } else {
+            let BenchResult { times, answer, .. } = bench(10, 0)?;
+            if answer != first_answer {
+                return Err(AocError::IncorrectAnswer);
+            }
+            // The median isn't thrown off by a few slow runs
+            Stats::new(&times).median
+        };
+
+        let iters_to_do = (bench_time.as_nanos() / test_time.as_nanos().max(1)).max(1);
+
+        let result = bench(iters_to_do as _, 0)?;
+        if result.answer != first_answer {
+            return Err(AocError::IncorrectAnswer);
+        }
+        // The warm-up in the first bench is what the allocation stats measured
+        Ok(BenchResult {
+            alloc: first.alloc,
+            ..result
+        })
+    }
+
     fn save(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
         let mut time = Duration::ZERO;
+        let mut parallel = self.start_parallel(day_parts)?;
         for &(day, ref parts) in day_parts {
-            time += self.save_day(day, parts)?;
+            time += self.save_day(day, parts, parallel.as_mut())?;
         }
         Ok(time)
     }
 
-    fn save_day(&mut self, day: u32, parts: &[u32]) -> Res<Duration> {
-        let file = self.get_input(day)?;
-
-        let ans_file_name = answer_file_name(day, self.test);
+    fn save_day(
+        &mut self,
+        day: u32,
+        parts: &[u32],
+        parallel: Option<&mut ParallelSolvers>,
+    ) -> Res<Duration> {
+        let ans_file_name = self.answer_file_name(day, self.test);
         let answers = if ans_file_name.exists() {
             std::fs::read_to_string(&ans_file_name)?
         } else {
@@ -586,7 +1397,7 @@ impl Settings {
         };
         let mut answer_vec: Vec<_> = answers.lines().map(Cow::Borrowed).collect();
 
-        let mut solver = self.day_to_solver(day, file)?;
+        let mut solver = self.day_solver(day, parallel)?;
         let mut total_time = Duration::ZERO;
 
         let mut buf = String::new();
@@ -594,11 +1405,7 @@ impl Settings {
         let parts = if parts.is_empty() { &[1, 2] } else { parts };
 
         for &part in parts {
-            let time = match part {
-                1 => solver.part_one(&mut buf),
-                2 => solver.part_two(&mut buf),
-                p => solver.run_any(p, &mut buf),
-            }?;
+            let time = solver.run_part(part, &mut buf)?.time;
             total_time += time;
 
             let part = part as usize - 1;
@@ -607,6 +1414,14 @@ impl Settings {
             }
             let saved = answer_vec[part].to_mut();
 
+            self.write_record(Record {
+                day,
+                part: part as u32 + 1,
+                answer: self.shown_answer(&buf),
+                duration_ns: Some(time.as_nanos() as u64),
+                ..Default::default()
+            })?;
+
             eprint!("d{day:02}p{:02}: ", part + 1);
 
             if !saved.is_empty() {
@@ -647,9 +1462,10 @@ impl Settings {
     fn validate(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
         let mut times = Duration::ZERO;
         let mut incorrect = 0;
+        let mut parallel = self.start_parallel(day_parts)?;
 
         for &(day, ref parts) in day_parts {
-            let (t, i) = self.validate_day(day, parts)?;
+            let (t, i) = self.validate_day(day, parts, parallel.as_mut())?;
             times += t;
             incorrect += i;
         }
@@ -662,10 +1478,13 @@ impl Settings {
         }
     }
 
-    fn validate_day(&mut self, day: u32, parts: &[u32]) -> Res<(Duration, u32)> {
-        let file = self.get_input(day)?;
-
-        let ans_file_name = answer_file_name(day, self.test);
+    fn validate_day(
+        &mut self,
+        day: u32,
+        parts: &[u32],
+        parallel: Option<&mut ParallelSolvers>,
+    ) -> Res<(Duration, u32)> {
+        let ans_file_name = self.answer_file_name(day, self.test);
         let answers = if ans_file_name.exists() {
             std::fs::read_to_string(&ans_file_name)?
         } else {
@@ -675,12 +1494,12 @@ impl Settings {
                 "Answer file {:?} missing, saving current answers",
                 ans_file_name
             );
-            let t = self.save_day(day, parts)?;
+            let t = self.save_day(day, parts, parallel)?;
             return Ok((t, 0));
         };
         let mut answer_vec: Vec<_> = answers.lines().map(Cow::Borrowed).collect();
 
-        let mut solver = self.day_to_solver(day, file)?;
+        let mut solver = self.day_solver(day, parallel)?;
         let mut total_time = Duration::ZERO;
         let mut buf = String::new();
         let mut incorrect = 0;
@@ -688,11 +1507,7 @@ impl Settings {
         let parts = if parts.is_empty() { &[1, 2] } else { parts };
 
         for &part in parts {
-            let time = match part {
-                1 => solver.part_one(&mut buf),
-                2 => solver.part_two(&mut buf),
-                p => solver.run_any(p, &mut buf),
-            }?;
+            let time = solver.run_part(part, &mut buf)?.time;
             total_time += time;
 
             let part = part as usize - 1;
@@ -701,6 +1516,15 @@ impl Settings {
             }
             let saved = answer_vec[part].to_mut();
 
+            self.write_record(Record {
+                day,
+                part: part as u32 + 1,
+                answer: self.shown_answer(&buf),
+                duration_ns: Some(time.as_nanos() as u64),
+                correct: (!saved.is_empty()).then(|| buf.eq(saved)),
+                ..Default::default()
+            })?;
+
             eprint!("d{day:02}p{:02}: ", part + 1);
 
             if !saved.is_empty() {
@@ -742,19 +1566,641 @@ impl Settings {
     }
 
     fn prompt(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Result<Duration, AocError> {
-        let api_key = &api_key()?;
         for &(day, _) in day_parts {
-            self.get_prompt(day, api_key)?;
+            self.get_prompt(day)?;
         }
         Ok(Duration::ZERO)
     }
 
-    fn day_to_solver(&self, day: u32, file: Vec<u8>) -> Res<ParentSolver> {
-        Ok(ParentSolver::new(day, &file, self.debug, self.release)?)
+    fn read(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        for &(day, _) in day_parts {
+            let path = self.prompt_file_name(day);
+            let saved = match std::fs::read_to_string(&path) {
+                Ok(html) => Some(Prompt::parse(&html, &self.day_url(day))),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            };
+            // Part 1 may have been solved anywhere, and asking again is cheap
+            // when the page hasn't changed
+            let fetch = match &saved {
+                None => true,
+                Some(prompt) => prompt.parts() < 2 && !self.offline,
+            };
+            let prompt = match saved {
+                Some(prompt) if !fetch => prompt,
+                _ => {
+                    self.get_prompt(day)?;
+                    Prompt::parse(&std::fs::read_to_string(&path)?, &self.day_url(day))
+                }
+            };
+
+            if self.markdown {
+                let md_path = self.prompt_markdown_file_name(day);
+                std::fs::write(&md_path, prompt.markdown())?;
+                eprintln!("Wrote {}", md_path.display());
+            } else {
+                let width = std::env::var("COLUMNS")
+                    .ok()
+                    .and_then(|c| c.parse().ok())
+                    .unwrap_or(PROMPT_WIDTH);
+                let mut stdout = stdout();
+                let styled = stdout.is_terminal();
+                stdout.write_all(prompt.text(width, styled).as_bytes())?;
+            }
+        }
+        Ok(Duration::ZERO)
     }
 
-    fn day_to_bencher(&self, day: u32, file: Vec<u8>) -> Res<ParentSolver> {
-        Ok(ParentSolver::new(day, &file, self.debug, true)?)
+    fn leaderboard(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        let id = self.leaderboard.ok_or(AocError::NoLeaderboard)?;
+        let json = self.leaderboard_json(id)?;
+        if self.output_format == OutputFormat::Json {
+            let mut stdout = stdout();
+            stdout.write_all(json.as_bytes())?;
+            stdout.write_all(b"\n")?;
+            return Ok(Duration::ZERO);
+        }
+
+        let board = Leaderboard::parse(&json)?;
+        eprint!("{}", board.table(days_in_year(self.year)));
+        let days: Vec<u32> = if day_parts.is_empty() {
+            board.last_day().into_iter().collect()
+        } else {
+            day_parts.iter().map(|&(day, _)| day).collect()
+        };
+        let day1 = board
+            .day1_ts
+            .unwrap_or_else(|| release_time(1, self.year).timestamp());
+        for day in days {
+            eprintln!();
+            eprint!(
+                "{}",
+                board.day_table(day, day1 + i64::from(day - 1) * 24 * 60 * 60)
+            );
+        }
+        Ok(Duration::ZERO)
+    }
+
+    /// The leaderboard's JSON, fetched again only when the saved copy is
+    /// older than [`LEADERBOARD_REFRESH_SECS`].
+    fn leaderboard_json(&self, id: u64) -> Res<String> {
+        let path = Path::new(LEADERBOARD_CACHE_DIR).join(format!("{}-{id}.json", self.year));
+        let age = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        if let Some(age) = age
+            && (self.offline || age.as_secs() < LEADERBOARD_REFRESH_SECS)
+        {
+            eprintln!("Leaderboard from {} minutes ago", age.as_secs() / 60);
+            return Ok(std::fs::read_to_string(&path)?);
+        }
+
+        let session = self.session()?;
+        let url = format!(
+            "{}/{}/leaderboard/private/view/{id}.json",
+            self.base_url(),
+            self.year
+        );
+        debug_println!(self.runner_debug, 1, "Fetching {url}");
+        let req = self.client()?.get(&url, &session.token)?;
+        let status = req.status();
+        let body = req.into_body().read_to_string()?;
+        if is_logged_out(status, &body) {
+            return Err(session.expired());
+        }
+        // Without access, the site redirects to an HTML page
+        if !status.is_success() || !body.trim_start().starts_with('{') {
+            return Err(AocError::site_response(url, status, body));
+        }
+        create_dir_all(LEADERBOARD_CACHE_DIR)?;
+        std::fs::write(&path, &body)?;
+        Ok(body)
+    }
+
+    /// Fetches the personal times page and writes its table into the README.
+    fn personal_stats(&mut self) -> Res<Duration> {
+        let url = format!("{}/{}/leaderboard/self", self.base_url(), self.year);
+        debug_println!(self.runner_debug, 1, "Fetching {url}");
+        let page = self.get_page(&url)?;
+        let body = page.text();
+        if !page.status.is_success() {
+            return Err(AocError::site_response(url, page.status, body));
+        }
+        let stats = PersonalStats::parse(&body);
+
+        let bench = if self.bench_stats {
+            let mut medians = BTreeMap::new();
+            for day in stats.days.iter().map(|d| d.day) {
+                for part in [1, 2] {
+                    let history: Vec<HistoryEntry> =
+                        read_json_lines(&self.bench_history_file_name(day, part))?;
+                    if let Some(entry) = history.iter().rev().find(|e| e.test == 0) {
+                        medians.insert((day, part), Duration::from_nanos(entry.median_ns));
+                    }
+                }
+            }
+            Some(medians)
+        } else {
+            None
+        };
+
+        let marker = if self.year == YEAR {
+            "personal-stats".to_string()
+        } else {
+            format!("personal-stats-{}", self.year)
+        };
+        let table = format!("```txt\n{}```\n", stats.table(bench.as_ref()));
+        let readme = std::fs::read_to_string(README)?;
+        let updated = replace_block(&readme, &marker, &table)
+            .ok_or_else(|| AocError::no_stats_block(README, marker))?;
+        std::fs::write(README, updated)?;
+        eprintln!(
+            "Wrote {} days of personal stats to {README}",
+            stats.days.len()
+        );
+        Ok(Duration::ZERO)
+    }
+
+    fn check_session(&mut self) -> Res<Duration> {
+        let session = self.session()?;
+        eprintln!("Using the session token from {}", session.source);
+
+        let url = format!("{}/{}", self.base_url(), self.year);
+        let req = self.client()?.get(&url, &session.token)?;
+        let status = req.status();
+        let body = req.into_body().read_to_string()?;
+        if !status.is_success() && !is_logged_out(status, &body) {
+            return Err(AocError::site_response(url, status, body));
+        }
+        match logged_in_user(&body) {
+            Some(user) => {
+                eprintln!("Logged in as {user}");
+                Ok(Duration::ZERO)
+            }
+            None => Err(session.expired()),
+        }
+    }
+
+    fn submit(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        if self.tests != Tests::One(0) {
+            return Err(AocError::SubmitTestInput);
+        }
+        let session = self.session()?;
+        let mut total_time = Duration::ZERO;
+        let mut buf = String::new();
+
+        for &(day, ref parts) in day_parts {
+            let path = self.submissions_file_name(day);
+            let mut attempts: Vec<Attempt> = read_json_lines(&path)?;
+            let parts = if parts.is_empty() {
+                vec![next_part(&attempts)]
+            } else {
+                parts.clone()
+            };
+
+            let mut solver = self.day_solver(day, None)?;
+            for part in parts {
+                total_time += solver.run_part(part, &mut buf)?.time;
+                let answer = buf.trim();
+                check_attempt(&attempts, part, answer)?;
+
+                eprintln!(
+                    "d{day:02}p{part:02}: submitting {}",
+                    self.shown_answer(answer).unwrap_or("")
+                );
+                let outcome = post_answer(
+                    self.client()?,
+                    self.base_url(),
+                    self.year,
+                    day,
+                    part,
+                    answer,
+                    &session,
+                )?;
+                eprintln!("d{day:02}p{part:02}: {outcome}");
+
+                let attempt = Attempt::new(part, answer, outcome);
+                append_json_line(&path, &attempt)?;
+                attempts.push(attempt);
+
+                if outcome != Outcome::Correct {
+                    return Err(AocError::NotAccepted(outcome.to_string().into()));
+                }
+            }
+        }
+        Ok(total_time)
+    }
+
+    fn new_days(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Duration> {
+        // Check everything first so nothing is half-created
+        for &(day, _) in day_parts {
+            if self.day_dir(day).exists() {
+                return Err(AocError::DayExists(day));
+            }
+        }
+
+        for &(day, _) in day_parts {
+            let dir = self.day_dir(day);
+            scaffold_day(&self.template, &dir, day, &self.package_name(day))?;
+            eprintln!("Created {}", dir.display());
+            if register_member(Path::new("Cargo.toml"), &dir.to_string_lossy())? {
+                eprintln!("Added {} to the workspace", dir.display());
+            }
+
+            // Fetching the input fetches the prompt too
+            if self.fetch && self.input_file_name(day, 0).exists() {
+                self.get_prompt(day)?;
+            } else if self.fetch {
+                self.get_input(day)?;
+            }
+        }
+        Ok(Duration::ZERO)
+    }
+
+    /// Writes a result to stdout if `--output-format` isn't text.
+    fn write_record(&mut self, record: Record) -> Res<()> {
+        let record = Record {
+            mode: self.mode.name(),
+            year: self.year,
+            test: self.test,
+            ..record
+        };
+        self.record_writer.write(self.output_format, &record)
+    }
+
+    fn shown_answer<'a>(&self, answer: &'a str) -> Option<&'a str> {
+        (!self.hide_answers).then_some(answer)
+    }
+
+    /// Gets the solver for the next day, either by starting one or by taking
+    /// the finished answers from the parallel solvers.
+    fn day_solver(&mut self, day: u32, parallel: Option<&mut ParallelSolvers>) -> Res<DaySolver> {
+        match parallel {
+            Some(parallel) => parallel.next_day(day),
+            None => {
+                let file = self.get_input(day)?;
+                self.day_to_solver(day, file)
+            }
+        }
+    }
+
+    /// Fetches every input and starts solving all days in the background if
+    /// `--parallel` was passed.
+    fn start_parallel(&mut self, day_parts: &[(u32, Vec<u32>)]) -> Res<Option<ParallelSolvers>> {
+        if !self.parallel {
+            return Ok(None);
+        }
+
+        let mut jobs = Vec::with_capacity(day_parts.len());
+        for &(day, ref parts) in day_parts {
+            let input = self.get_input(day)?;
+            let parts = if parts.is_empty() {
+                vec![1, 2]
+            } else {
+                parts.clone()
+            };
+            jobs.push(DayJob {
+                day,
+                parts,
+                input,
+                binary: self.binaries.get(&day).cloned(),
+                options: self.solver_options(day, self.day_release(day)),
+            });
+        }
+
+        let threads = self
+            .jobs
+            .or_else(|| std::thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+            .min(jobs.len());
+        debug_println!(
+            self.runner_debug,
+            1,
+            "Running {} days on {threads} threads",
+            jobs.len()
+        );
+
+        let jobs = jobs.into_iter().map(|job| move || job.solve()).collect();
+        Ok(Some(ParallelSolvers::start(jobs, threads)))
+    }
+
+    fn day_to_solver(&self, day: u32, file: Vec<u8>) -> Res<DaySolver> {
+        let binary = self.binaries.get(&day).map(PathBuf::as_path);
+        let options = self.solver_options(day, self.day_release(day));
+        let solver = options.start(day, &file, binary)?;
+        Ok(DaySolver::Live { solver, options })
+    }
+
+    fn day_to_bencher(&self, day: u32, file: Vec<u8>) -> Res<(ParentSolver, SolverOptions)> {
+        let binary = self.binaries.get(&day).map(PathBuf::as_path);
+        let options = self.solver_options(day, true);
+        Ok((options.start(day, &file, binary)?, options))
+    }
+
+    fn solver_options(&self, day: u32, release: bool) -> SolverOptions {
+        let millis = Duration::from_millis;
+        let timeout = self
+            .day_setting("timeout", day, |d| d.timeout)
+            .or(self.timeout);
+        let part_timeouts = self
+            .day_setting("timeout", day, |d| Some(&d.parts))
+            .into_iter()
+            .flatten()
+            .filter_map(|(part, config)| Some((part.parse().ok()?, millis(config.timeout?))))
+            .collect();
+        let memory_limit = self
+            .day_setting("memory_limit", day, |d| d.memory_limit)
+            .or(self.memory_limit);
+
+        SolverOptions {
+            package: self.package_name(day),
+            debug: self
+                .day_setting("debug", day, |d| d.debug)
+                .unwrap_or(self.debug),
+            release,
+            limits: Limits {
+                timeout: timeout.map(millis),
+                memory: memory_limit.map(|mib| mib * 1024 * 1024),
+            },
+            part_timeouts,
+            alloc_stats: self.alloc_stats,
+            in_process: self.in_process,
+        }
+    }
+
+    /// Whether the day is built with the release profile outside of bench
+    /// mode.
+    fn day_release(&self, day: u32) -> bool {
+        self.day_setting("release", day, |d| d.release)
+            .unwrap_or(self.release)
+    }
+
+    /// A setting from the day's table in `aoc.toml`, unless the flag with this
+    /// `id` was given on the command line.
+    fn day_setting<'a, T>(
+        &'a self,
+        id: &str,
+        day: u32,
+        get: impl FnOnce(&'a DayConfig) -> Option<T>,
+    ) -> Option<T> {
+        if self.from_cli.contains(id) {
+            return None;
+        }
+        self.day_configs.get(&(self.year, day)).and_then(get)
+    }
+}
+
+/// Everything needed to start a day's solver, so parallel workers can start
+/// them too.
+#[derive(Debug, Clone)]
+struct SolverOptions {
+    package: String,
+    debug: u8,
+    release: bool,
+    limits: Limits,
+    /// Parts with a different timeout than the rest of the day.
+    part_timeouts: BTreeMap<u32, Duration>,
+    alloc_stats: bool,
+    in_process: bool,
+}
+
+impl SolverOptions {
+    /// The timeout for a part, or for parsing with [`PARSE_PART`].
+    fn timeout(&self, part: u32) -> Option<Duration> {
+        self.part_timeouts
+            .get(&part)
+            .copied()
+            .or(self.limits.timeout)
+    }
+
+    /// Starts the day's solver, using the prebuilt `binary` if there is one.
+    fn start(&self, day: u32, input: &[u8], binary: Option<&Path>) -> Res<ParentSolver> {
+        if self.in_process {
+            return start_in_process(day, input, self.debug);
+        }
+        if let Some(binary) = binary {
+            return Ok(ParentSolver::from_binary(
+                binary,
+                input,
+                self.debug,
+                self.limits,
+            )?);
+        }
+        Ok(ParentSolver::new(
+            &self.package,
+            input,
+            self.debug,
+            self.release,
+            self.limits,
+            self.alloc_stats,
+        )?)
+    }
+}
+
+#[cfg(feature = "in-process")]
+fn start_in_process(day: u32, input: &[u8], debug: u8) -> Res<ParentSolver> {
+    crate::days::start(day, input, debug)
+}
+
+#[cfg(not(feature = "in-process"))]
+fn start_in_process(_day: u32, _input: &[u8], _debug: u8) -> Res<ParentSolver> {
+    Err(AocError::InProcessUnavailable("in-process"))
+}
+
+/// A day whose input has been read, waiting for a parallel worker.
+struct DayJob {
+    day: u32,
+    parts: Vec<u32>,
+    input: Vec<u8>,
+    binary: Option<PathBuf>,
+    options: SolverOptions,
+}
+
+/// The answer and time for one part, as computed by a parallel worker.
+struct PartAnswer {
+    part: u32,
+    answer: Result<(String, PartStats), Panic>,
+}
+
+/// Everything a parallel worker computed for one day.
+struct DayAnswers {
+    parse_time: Result<Option<Duration>, Panic>,
+    parts: Vec<PartAnswer>,
+}
+
+impl DayJob {
+    fn solve(self) -> Res<DayAnswers> {
+        let options = &self.options;
+        let mut solver = options.start(self.day, &self.input, self.binary.as_deref())?;
+        let parse_time = match solver.parse() {
+            Err(SolverError::Panicked(panic)) => {
+                return Ok(DayAnswers {
+                    parse_time: Err(panic),
+                    parts: Vec::new(),
+                });
+            }
+            result => Ok(result?),
+        };
+        let mut answers = Vec::with_capacity(self.parts.len());
+        for part in self.parts {
+            let mut answer = String::new();
+            solver.set_timeout(options.timeout(part));
+            let stats = match part {
+                1 => solver.part_one(&mut answer),
+                2 => solver.part_two(&mut answer),
+                p => solver.run_any(p, &mut answer),
+            };
+            // Keep going after a panic like a live solver would
+            let answer = match stats {
+                Ok(stats) => Ok((answer, stats)),
+                Err(SolverError::Panicked(panic)) => Err(panic),
+                Err(e) => return Err(e.into()),
+            };
+            answers.push(PartAnswer { part, answer });
+        }
+        Ok(DayAnswers {
+            parse_time,
+            parts: answers,
+        })
+    }
+}
+
+/// Solvers running on background threads for `--parallel`.
+///
+/// Days finish in any order, but [`ParallelSolvers::next_day`] hands them out
+/// in the order they were queued.
+struct ParallelSolvers {
+    results: Receiver<(usize, Res<DayAnswers>)>,
+    finished: BTreeMap<usize, Res<DayAnswers>>,
+    next: usize,
+    stop: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ParallelSolvers {
+    /// Runs each job on one of `threads` workers. A job is usually a
+    /// [`DayJob`], but anything that gives a day's answers works.
+    fn start<J>(jobs: Vec<J>, threads: usize) -> Self
+    where
+        J: FnOnce() -> Res<DayAnswers> + Send + 'static,
+    {
+        let queue = Arc::new(Mutex::new(jobs.into_iter().enumerate()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (send, results) = channel();
+
+        let workers = (0..threads)
+            .map(|_| {
+                let queue = queue.clone();
+                let stop = stop.clone();
+                let send = send.clone();
+                std::thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        // Release the lock before solving
+                        let Some((i, job)) = queue.lock().unwrap().next() else {
+                            break;
+                        };
+                        if send.send((i, job())).is_err() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            results,
+            finished: BTreeMap::new(),
+            next: 0,
+            stop,
+            workers,
+        }
+    }
+
+    /// Waits for the next day in queue order to finish.
+    fn next_day(&mut self, day: u32) -> Res<DaySolver> {
+        let answers = loop {
+            if let Some(answers) = self.finished.remove(&self.next) {
+                break answers;
+            }
+            // Workers only hang up early if a job panicked
+            let Ok((i, answers)) = self.results.recv() else {
+                return Err(AocError::WorkerStopped(day));
+            };
+            self.finished.insert(i, answers);
+        };
+        self.next += 1;
+        let DayAnswers { parse_time, parts } = answers?;
+        Ok(DaySolver::Finished {
+            parse_time,
+            answers: parts.into_iter(),
+        })
+    }
+}
+
+impl Drop for ParallelSolvers {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Either a running solver or the answers from one that already finished.
+enum DaySolver {
+    Live {
+        solver: ParentSolver,
+        options: SolverOptions,
+    },
+    Finished {
+        parse_time: Result<Option<Duration>, Panic>,
+        answers: std::vec::IntoIter<PartAnswer>,
+    },
+}
+
+impl DaySolver {
+    fn parse(&mut self) -> Res<Option<Duration>> {
+        match self {
+            DaySolver::Live { solver, options } => {
+                solver.set_timeout(options.timeout(PARSE_PART));
+                Ok(solver.parse()?)
+            }
+            DaySolver::Finished { parse_time, .. } => {
+                Ok(parse_time.clone().map_err(SolverError::Panicked)?)
+            }
+        }
+    }
+
+    fn run_part(&mut self, part: u32, buffer: &mut String) -> Res<PartStats> {
+        match self {
+            DaySolver::Live { solver, options } => {
+                solver.set_timeout(options.timeout(part));
+                Ok(match part {
+                    1 => solver.part_one(buffer),
+                    2 => solver.part_two(buffer),
+                    p => solver.run_any(p, buffer),
+                }?)
+            }
+            DaySolver::Finished {
+                parse_time,
+                answers,
+            } => {
+                let Some(ans) = answers.next() else {
+                    // Workers stop after a parse panic, which every part then
+                    // reports like a live solver would
+                    let panic = parse_time
+                        .clone()
+                        .expect_err("parallel worker skipped a part");
+                    return Err(SolverError::Panicked(panic).into());
+                };
+                debug_assert_eq!(ans.part, part);
+                let (answer, stats) = ans.answer.map_err(SolverError::Panicked)?;
+                buffer.clear();
+                buffer.push_str(&answer);
+                Ok(stats)
+            }
+        }
     }
 }
 
@@ -765,30 +2211,32 @@ enum InputMessage {
     Exit,
 }
 
-fn average<N>(test_times: &[N]) -> N::Output
-where
-    N: for<'a> Sum<&'a N> + Div<u32>,
-{
-    test_times.iter().sum::<N>() / test_times.len() as u32
+/// Ids of the arguments given on the command line. `--no-<flag>` counts as
+/// giving `<flag>`, so it wins over config files too.
+fn cli_ids(matches: &ArgMatches) -> BTreeSet<String> {
+    use clap::parser::ValueSource;
+
+    matches
+        .ids()
+        .filter(|id| matches.value_source(id.as_str()) == Some(ValueSource::CommandLine))
+        .map(|id| {
+            let id = id.as_str();
+            id.strip_prefix("no_").unwrap_or(id).to_string()
+        })
+        .collect()
 }
 
-fn api_key() -> Result<String, AocError> {
-    use std::io::ErrorKind;
-    let mut key = match std::fs::read_to_string("./API_KEY") {
-        Ok(key) => key,
-        Err(e) => match e.kind() {
-            ErrorKind::NotFound => return Err(AocError::NoApiKey),
-            _ => return Err(AocError::ApiKeyIo { source: e }),
-        },
-    };
-    while key.chars().last().is_some_and(|c| c.is_whitespace()) {
-        key.pop();
+/// Account names end up in paths and environment variable names.
+fn parse_account(account: &str) -> Result<String, String> {
+    if !account.is_empty()
+        && account
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(account.to_string())
+    } else {
+        Err("account names can only have letters, digits, `-`, and `_`".to_string())
     }
-    Ok(key)
-}
-
-fn read_to_vec(req: Response<Body>) -> Res<Vec<u8>> {
-    Ok(req.into_body().read_to_vec()?)
 }
 
 fn readable_time<W: Write>(
@@ -808,53 +2256,261 @@ fn readable_time<W: Write>(
     }
 }
 
-fn print_times<D: Display>(day: u32, part: u32, ans: D, time: Duration) {
-    eprintln!("d{day:02}p{part:02}: ({time:?}) {ans}");
+/// Where wait mode gets the time from, so tests can skip the waiting.
+trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&mut self, duration: Duration);
 }
 
-fn prompt(day: u32) -> PathBuf {
-    let mut name = input_base_name(day);
-    name.push("prompt.html");
-    name
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
 }
 
-fn answer_file_name(day: u32, test: u8) -> PathBuf {
-    let mut name = input_base_name(day);
-    if test > 0 {
-        name.push(format!("answer{test:02}.txt"));
-    } else {
-        name.push("answer.txt");
+/// Shows the time left until `release`, updating every second when `live`,
+/// and returns once it's passed.
+fn countdown(
+    day: u32,
+    release: DateTime<Utc>,
+    clock: &mut impl Clock,
+    out: &mut impl Write,
+    live: bool,
+) -> std::io::Result<()> {
+    let mut shown = false;
+    loop {
+        let left = release - clock.now();
+        if left <= TimeDelta::zero() {
+            break;
+        }
+        // Whole seconds left, rounded up so it reaches 0 at release
+        let secs = (left.num_milliseconds() + 999) / 1000;
+        if live || !shown {
+            write!(
+                out,
+                "\r\x1b[KDay {day} releases in {:02}:{:02}:{:02}",
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60
+            )?;
+            if !live {
+                writeln!(out)?;
+            }
+            out.flush()?;
+            shown = true;
+        }
+        let to_next_second = left.num_milliseconds() - (secs - 1) * 1000;
+        clock.sleep(Duration::from_millis(to_next_second as u64));
+    }
+    if live && shown {
+        writeln!(out, "\r\x1b[KDay {day} is out")?;
     }
-    name
+    Ok(())
+}
+
+/// A random duration up to `max`.
+fn jitter(max: Duration) -> Duration {
+    let random = RandomState::new().build_hasher().finish();
+    max.mul_f64(random as f64 / u64::MAX as f64)
 }
 
-fn input_file_name(day: u32, test: u8) -> PathBuf {
-    let mut name = input_base_name(day);
-    if test > 0 {
-        name.push(format!("input{test:02}.txt"));
+fn print_stats(day: u32, part: u32, stats: &Stats, answer: &str) {
+    let stderr = stderr();
+    let time = |t| readable_time(&stderr, t, 3).unwrap();
+
+    if part == PARSE_PART {
+        eprint!("d{day:02} parse: avg ");
+    } else {
+        eprint!("d{day:02}p{part:02}: avg ");
+    }
+    time(stats.mean);
+    eprint!(", med ");
+    time(stats.median);
+    if part == PARSE_PART {
+        eprintln!();
     } else {
-        name.push("input.txt");
+        eprintln!(" ({answer:?})");
+    }
+
+    eprint!("        {:.0}% CI ", CONFIDENCE * 100.0);
+    time(stats.ci.0);
+    eprint!(" to ");
+    time(stats.ci.1);
+    eprint!(", min ");
+    time(stats.min);
+    eprint!(", max ");
+    time(stats.max);
+    eprint!(", sd ");
+    time(stats.stddev);
+    eprint!(", MAD ");
+    time(stats.mad);
+    eprintln!();
+
+    let outliers = stats.outliers;
+    eprint!("        {} samples", stats.samples);
+    if outliers.total() > 0 {
+        eprint!(
+            ", {} outliers ({} mild, {} severe)",
+            outliers.total(),
+            outliers.mild(),
+            outliers.severe()
+        );
     }
-    name
+    eprintln!();
+}
+
+fn print_times<D: Display>(day: u32, part: u32, ans: D, time: Duration) {
+    eprintln!("d{day:02}p{part:02}: ({time:?}) {ans}");
 }
 
-fn input_base_name(day: u32) -> PathBuf {
-    PathBuf::from(format!("./inputs/day{day:02}"))
+fn print_alloc(alloc: &AllocStats) {
+    eprintln!(
+        "        heap peak {}, {} allocations, {} allocated",
+        readable_bytes(alloc.peak_bytes),
+        alloc.allocations,
+        readable_bytes(alloc.allocated_bytes)
+    );
 }
 
-fn parse_day(word: &str) -> Res<Vec<(u32, Vec<u32>)>> {
-    let mut nums = word.split('.');
-    let day = if let Some(n) = nums.next() {
-        if n.is_empty() {
-            Err(AocError::no_day_specified(word))
+fn readable_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Where things are saved for each day.
+impl Settings {
+    /// The cargo package of a day's solver. Days from [`YEAR`] are plain
+    /// `day05`, and other years are qualified like `y2024-day05`.
+    fn package_name(&self, day: u32) -> String {
+        if self.year == YEAR {
+            format!("day{day:02}")
         } else {
-            n.parse().map_err(|_| AocError::parse(n, word))
+            format!("y{}-day{day:02}", self.year)
         }
-    } else {
-        Err(AocError::EmptyArgument)
-    }?;
+    }
+
+    fn day_dir(&self, day: u32) -> PathBuf {
+        let mut dir = PathBuf::from("days");
+        dir.push(self.package_name(day));
+        dir
+    }
 
-    let rest = nums
+    fn prompt_file_name(&self, day: u32) -> PathBuf {
+        let mut name = self.input_base_name(day);
+        name.push("prompt.html");
+        name
+    }
+
+    /// Lists the examples saved as test inputs, which part each is from, and
+    /// their answers.
+    fn examples_file_name(&self, day: u32) -> PathBuf {
+        let mut name = self.input_base_name(day);
+        name.push("examples.json");
+        name
+    }
+
+    fn prompt_markdown_file_name(&self, day: u32) -> PathBuf {
+        let mut name = self.input_base_name(day);
+        name.push("prompt.md");
+        name
+    }
+
+    fn answer_file_name(&self, day: u32, test: u8) -> PathBuf {
+        let mut name = self.input_base_name(day);
+        if test > 0 {
+            name.push(format!("answer{test:02}.txt"));
+        } else {
+            name.push("answer.txt");
+        }
+        name
+    }
+
+    fn submissions_file_name(&self, day: u32) -> PathBuf {
+        let mut name = self.input_base_name(day);
+        name.push("submissions.jsonl");
+        name
+    }
+
+    fn bench_history_file_name(&self, day: u32, part: u32) -> PathBuf {
+        let mut name = PathBuf::from(BENCH_HISTORY_DIR);
+        name.push(self.year.to_string());
+        name.push(format!("day{day:02}"));
+        name.push(format!("part{part}.jsonl"));
+        name
+    }
+
+    fn input_file_name(&self, day: u32, test: u8) -> PathBuf {
+        let mut name = self.input_base_name(day);
+        if test > 0 {
+            name.push(format!("input{test:02}.txt"));
+        } else {
+            name.push("input.txt");
+        }
+        name
+    }
+
+    /// Directory for a day's inputs, answers, and other saved files. Kept
+    /// separately for each named account, since inputs differ by account, and
+    /// for each year besides [`YEAR`].
+    fn input_base_name(&self, day: u32) -> PathBuf {
+        let mut name = PathBuf::from("./inputs");
+        if let Some(account) = &self.account {
+            name.push(account);
+        }
+        if self.year != YEAR {
+            name.push(self.year.to_string());
+        }
+        name.push(format!("day{day:02}"));
+        name
+    }
+}
+
+/// Days picked by one argument.
+#[derive(Debug, PartialEq, Eq)]
+enum Selection {
+    /// Days to run, with their parts.
+    Days(DayParts),
+    /// Days to leave out, written like `!9`.
+    Exclude(Vec<u32>),
+}
+
+/// Parses `days.part.part`, optionally with a year like `2024/days.part`, or
+/// `!days` to leave days out. `days` is a day, a range like `3-7`, `0` for the
+/// whole year, or `last` or `today`. Returns the year, or `year` if none was
+/// given, and what was selected.
+fn parse_day(word: &str, year: u32) -> Res<(u32, Selection)> {
+    let (year, rest) = match word.split_once('/') {
+        Some((y, rest)) => (y.parse().map_err(|_| AocError::parse(y, word))?, rest),
+        None => (year, word),
+    };
+    let (exclude, rest) = match rest.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+
+    let mut nums = rest.split('.');
+    let days = match nums.next() {
+        Some("") => return Err(AocError::no_day_specified(word)),
+        Some(days) => parse_days(days, word, year)?,
+        None => return Err(AocError::EmptyArgument),
+    };
+
+    let parts = nums
         .map(|n| {
             if n.is_empty() {
                 Err(AocError::empty_part(word))
@@ -864,36 +2520,262 @@ fn parse_day(word: &str) -> Res<Vec<(u32, Vec<u32>)>> {
         })
         .collect::<Res<Vec<u32>>>()?;
 
-    Ok(if day == 0 {
-        (1..=12).map(|n| (n, rest.clone())).collect()
+    let selection = if exclude {
+        if !parts.is_empty() {
+            return Err(AocError::excluded_parts(word));
+        }
+        Selection::Exclude(days)
     } else {
-        vec![(day, rest)]
+        Selection::Days(days.into_iter().map(|day| (day, parts.clone())).collect())
+    };
+    Ok((year, selection))
+}
+
+/// The days written as a number, range, or keyword.
+fn parse_days(days: &str, word: &str, year: u32) -> Res<Vec<u32>> {
+    let num = |n: &str| n.parse::<u32>().map_err(|_| AocError::parse(n, word));
+    Ok(match days {
+        "0" => (1..=days_in_year(year)).collect(),
+        "last" => vec![last_released_day(year)?],
+        "today" => vec![todays_day(year)?],
+        _ => match days.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (num(first)?, num(last)?);
+                if first > last {
+                    return Err(AocError::empty_range(word));
+                }
+                // Days past the end are skipped later, so only the first of
+                // them is kept to be reported
+                (first..=last.min(days_in_year(year).max(first))).collect()
+            }
+            None => vec![num(days)?],
+        },
     })
 }
 
-/// Returns `None` if the input is released, otherwise returns the time until
-/// release. Returns `None` if the time cannot be determined.
-///
-/// # Warning
-///
-/// This is likely to break (by not allowing downloading of the puzzle for an
-/// extra hour) if the United States decides to remove time changes in favor of
-/// sticking to Daylight Saving Time, and Eric Wastl continues to keep AoC on
-/// US-East time. In such an event, change `ERIC_TIME_OFFSET` to `-4`.
-// Note: chrono is actually way more confusing than I thought. Idk if this is
-// the correct way to use it but it seems to work.
-fn time_until_input_is_released(day: u32, year: u32) -> TimeDelta {
-    const ERIC_TIME_OFFSET: i32 = -5;
+/// Number of puzzles in a year's calendar.
+fn days_in_year(year: u32) -> u32 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// The latest day of `year` that has been released.
+fn last_released_day(year: u32) -> Res<u32> {
+    (1..=days_in_year(year))
+        .rev()
+        .find(|&day| time_until_input_is_released(day, year) <= TimeDelta::zero())
+        .ok_or(AocError::NothingReleased(year))
+}
+
+/// The first day of `year` that hasn't been released by `now`.
+fn next_release(year: u32, now: DateTime<Utc>) -> Res<u32> {
+    (1..=days_in_year(year))
+        .find(|&day| release_time(day, year) > now)
+        .ok_or(AocError::AllReleased(year))
+}
 
-    let t = Utc::now().naive_utc();
+/// The day of `year` that releases within the hour, or else the one released
+/// in the last day. Inputs are waited for when they're this close to release.
+fn todays_day(year: u32) -> Res<u32> {
+    (1..=days_in_year(year))
+        .rev()
+        .find(|&day| {
+            let until = time_until_input_is_released(day, year);
+            until <= TimeDelta::hours(1) && until > -TimeDelta::days(1)
+        })
+        .ok_or(AocError::NoPuzzleToday(year))
+}
+
+/// Time until the input is released, negative once it's out.
+fn time_until_input_is_released(day: u32, year: u32) -> TimeDelta {
+    release_time(day, year) - Utc::now()
+}
 
-    let release = NaiveDate::from_ymd_opt(year as _, 12, day)
+/// When a day's puzzle and input come out: midnight in New York, with
+/// whatever offset the tz database has for that date.
+fn release_time(day: u32, year: u32) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(year as _, 12, day)
         .unwrap()
         .and_hms_opt(0, 0, 0)
         .unwrap()
-        .and_local_timezone(FixedOffset::east_opt(ERIC_TIME_OFFSET * 60 * 60).unwrap())
+        .and_local_timezone(New_York)
+        .earliest()
         .unwrap()
-        .naive_utc();
+        .to_utc()
+}
+
+#[test]
+fn cli_beats_day_config_t() {
+    use clap::{CommandFactory, FromArgMatches};
+
+    let matches = with_negations(Settings::command())
+        .try_get_matches_from(["aoc", "--no-release", "--debug"])
+        .unwrap();
+    let mut settings = Settings::from_arg_matches(&matches).unwrap();
+    settings.from_cli = cli_ids(&matches);
+    let config = DayConfig {
+        release: Some(true),
+        debug: Some(3),
+        ..DayConfig::default()
+    };
+    settings.day_configs = BTreeMap::from([((settings.year, 10), config)]);
+
+    assert!(!settings.day_release(10));
+    assert_eq!(settings.day_setting("debug", 10, |d| d.debug), None);
+    assert_eq!(settings.day_setting("release", 9, |d| d.release), None);
+}
+
+#[test]
+fn parallel_order_t() {
+    // Later days finish first
+    let job = |day: u32| {
+        move || {
+            std::thread::sleep(Duration::from_millis(u64::from(5 - day) * 20));
+            Ok(DayAnswers {
+                parse_time: Ok(None),
+                parts: vec![PartAnswer {
+                    part: 1,
+                    answer: Ok((
+                        day.to_string(),
+                        PartStats {
+                            time: Duration::ZERO,
+                            alloc: None,
+                        },
+                    )),
+                }],
+            })
+        }
+    };
+    let mut parallel = ParallelSolvers::start((1..=4).map(job).collect(), 4);
+    let mut buffer = String::new();
+    for day in 1..=4 {
+        let mut solver = parallel.next_day(day).unwrap();
+        solver.run_part(1, &mut buffer).unwrap();
+        assert_eq!(buffer, day.to_string());
+    }
 
-    release - t
+    let jobs: Vec<fn() -> Res<DayAnswers>> = vec![|| panic!("worker died")];
+    let mut parallel = ParallelSolvers::start(jobs, 1);
+    assert!(matches!(
+        parallel.next_day(7),
+        Err(AocError::WorkerStopped(7))
+    ));
+}
+
+#[test]
+fn finished_parse_panic_t() {
+    let panic = Panic {
+        message: "bad input".into(),
+        location: None,
+        backtrace: None,
+    };
+    let mut solver = DaySolver::Finished {
+        parse_time: Err(panic),
+        answers: Vec::new().into_iter(),
+    };
+    let mut buffer = String::new();
+    for part in [1, 2] {
+        let e = solver.run_part(part, &mut buffer).unwrap_err();
+        assert!(e.is_panic(), "{e}");
+    }
+}
+
+#[test]
+fn release_time_t() {
+    let utc = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+    for (day, year, release) in [
+        (1, 2015, "2015-12-01T05:00:00Z"),
+        (25, 2019, "2019-12-25T05:00:00Z"),
+        (1, 2020, "2020-12-01T05:00:00Z"),
+        (14, 2023, "2023-12-14T05:00:00Z"),
+        (1, 2024, "2024-12-01T05:00:00Z"),
+        (12, 2025, "2025-12-12T05:00:00Z"),
+    ] {
+        assert_eq!(release_time(day, year), utc(release), "{year} day {day}");
+    }
+}
+
+#[test]
+fn wait_t() {
+    struct FakeClock {
+        now: DateTime<Utc>,
+        slept: Vec<Duration>,
+    }
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now
+        }
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration;
+            self.slept.push(duration);
+        }
+    }
+
+    let utc = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+    assert_eq!(next_release(2025, utc("2025-11-30T12:00:00Z")).unwrap(), 1);
+    assert_eq!(next_release(2025, utc("2025-12-03T04:59:59Z")).unwrap(), 3);
+    assert_eq!(next_release(2025, utc("2025-12-03T05:00:00Z")).unwrap(), 4);
+    assert!(matches!(
+        next_release(2025, utc("2025-12-12T05:00:00Z")),
+        Err(AocError::AllReleased(2025))
+    ));
+
+    let release = release_time(4, 2025);
+    let mut clock = FakeClock {
+        now: release - TimeDelta::milliseconds(2300),
+        slept: Vec::new(),
+    };
+    let mut out = Vec::new();
+    countdown(4, release, &mut clock, &mut out, true).unwrap();
+    assert_eq!(clock.now, release);
+    // The first sleep lines the rest up with whole seconds
+    assert_eq!(clock.slept, [300, 1000, 1000].map(Duration::from_millis));
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\r\x1b[KDay 4 releases in 00:00:03\
+         \r\x1b[KDay 4 releases in 00:00:02\
+         \r\x1b[KDay 4 releases in 00:00:01\
+         \r\x1b[KDay 4 is out\n"
+    );
+
+    // Without a terminal, it's only shown once
+    clock.now = release - TimeDelta::seconds(3661);
+    let mut out = Vec::new();
+    countdown(4, release, &mut clock, &mut out, false).unwrap();
+    assert_eq!(clock.now, release);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "\r\x1b[KDay 4 releases in 01:01:01\n"
+    );
+
+    // Nothing to wait for once it's out
+    let mut out = Vec::new();
+    clock.slept.clear();
+    countdown(4, release, &mut clock, &mut out, true).unwrap();
+    assert!(out.is_empty() && clock.slept.is_empty());
+}
+
+#[test]
+fn parse_day_t() {
+    let days = |word| match parse_day(word, 2025).unwrap() {
+        (year, Selection::Days(days)) => (year, days),
+        (_, Selection::Exclude(_)) => panic!("{word} excluded days"),
+    };
+    assert_eq!(days("5"), (2025, vec![(5, vec![])]));
+    assert_eq!(days("2024/5.1"), (2024, vec![(5, vec![1])]));
+    assert_eq!(days("0").1.len(), 12);
+    assert_eq!(days("2024/0").1.len(), 25);
+    assert_eq!(days("3-5.2").1, [(3, vec![2]), (4, vec![2]), (5, vec![2])]);
+    assert_eq!(days("2015/last").1, [(25, vec![])]);
+    assert_eq!(days("11-4000000000").1, [(11, vec![]), (12, vec![])]);
+    assert_eq!(days("20-4000000000").1, [(20, vec![])]);
+    assert_eq!(
+        parse_day("2024/!9", 2025).unwrap(),
+        (2024, Selection::Exclude(vec![9]))
+    );
+
+    assert!(parse_day("2024/", 2025).is_err());
+    assert!(parse_day("x/5", 2025).is_err());
+    assert!(parse_day("7-3", 2025).is_err());
+    assert!(parse_day("!9.1", 2025).is_err());
+    assert!(parse_day("2015/today", 2025).is_err());
 }
@@ -0,0 +1,103 @@
+use std::fs::{create_dir_all, read_dir};
+use std::path::Path;
+
+use crate::{AocError, Res};
+
+/// Text in template files and names that's filled in for each day.
+struct Placeholders {
+    /// `{{day}}`: the two-digit day.
+    day: String,
+    /// `{{package}}`: the cargo package, like `day05` or `y2024-day05`.
+    package: String,
+    /// `{{crate}}`: the package as a Rust identifier, like `y2024_day05`.
+    krate: String,
+}
+
+impl Placeholders {
+    fn fill(&self, text: &str) -> String {
+        text.replace("{{day}}", &self.day)
+            .replace("{{package}}", &self.package)
+            .replace("{{crate}}", &self.krate)
+    }
+}
+
+/// Copies the template directory to `dir`, filling in the day and package.
+/// Fails without writing anything if `dir` already exists.
+pub fn scaffold_day(template: &Path, dir: &Path, day: u32, package: &str) -> Res<()> {
+    if dir.exists() {
+        return Err(AocError::DayExists(day));
+    }
+    if !template.is_dir() {
+        return Err(AocError::no_template(template.display().to_string()));
+    }
+    let placeholders = Placeholders {
+        day: format!("{day:02}"),
+        package: package.to_string(),
+        krate: package.replace('-', "_"),
+    };
+    copy_template(template, dir, &placeholders)
+}
+
+fn copy_template(from: &Path, to: &Path, placeholders: &Placeholders) -> Res<()> {
+    create_dir_all(to)?;
+    for entry in read_dir(from)? {
+        let entry = entry?;
+        let name = placeholders.fill(&entry.file_name().to_string_lossy());
+        let path = to.join(name);
+        if entry.file_type()?.is_dir() {
+            copy_template(&entry.path(), &path, placeholders)?;
+        } else {
+            let contents = std::fs::read_to_string(entry.path())?;
+            std::fs::write(path, placeholders.fill(&contents))?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds `dir` to the workspace members in `manifest` unless one of them
+/// already covers it. Returns whether the manifest was changed.
+pub fn register_member(manifest: &Path, dir: &str) -> Res<bool> {
+    let text = std::fs::read_to_string(manifest)?;
+    let Some(start) = text.find("members = [") else {
+        return Err(AocError::NoWorkspaceMembers);
+    };
+    let list_start = start + "members = [".len();
+    let Some(len) = text[list_start..].find(']') else {
+        return Err(AocError::NoWorkspaceMembers);
+    };
+    let list = &text[list_start..list_start + len];
+
+    let covered = list
+        .split(',')
+        .map(|member| member.trim().trim_matches('"'))
+        .any(|member| member_covers(member, dir));
+    if covered {
+        return Ok(false);
+    }
+
+    let separator = if list.trim().is_empty() { "" } else { ", " };
+    let mut text = text;
+    text.insert_str(list_start + len, &format!("{separator}\"{dir}\""));
+    std::fs::write(manifest, text)?;
+    Ok(true)
+}
+
+/// Whether a workspace member, which may end in a `/*` glob, includes `dir`.
+fn member_covers(member: &str, dir: &str) -> bool {
+    match member.strip_suffix("/*") {
+        Some(parent) => dir
+            .strip_prefix(parent)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .is_some_and(|rest| !rest.is_empty() && !rest.contains('/')),
+        None => member == dir,
+    }
+}
+
+#[test]
+fn member_covers_t() {
+    assert!(member_covers("days/*", "days/day05"));
+    assert!(member_covers("days/day05", "days/day05"));
+    assert!(!member_covers("days/*", "days/2024/day05"));
+    assert!(!member_covers("days/*", "daysx/day05"));
+    assert!(!member_covers("helpers", "days/day05"));
+}
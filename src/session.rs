@@ -0,0 +1,160 @@
+use std::fmt::Display;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use ureq::http::StatusCode;
+
+use crate::config::user_config_dir;
+use crate::{AocError, Res};
+
+/// Environment variable checked first for the session token.
+const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Per-project token file, relative to the working directory.
+const PROJECT_FILE: &str = "API_KEY";
+
+/// A session token and where it was found.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub token: String,
+    pub source: TokenSource,
+}
+
+#[derive(Debug, Clone)]
+pub enum TokenSource {
+    Env(String),
+    File(PathBuf),
+}
+
+impl Display for TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenSource::Env(var) => write!(f, "${var}"),
+            TokenSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl Session {
+    /// Finds the session token for `account`, or the default account. Checks,
+    /// in order:
+    ///
+    /// - `$AOC_SESSION`, or `$AOC_SESSION_<ACCOUNT>`
+    /// - `./API_KEY`, or `./API_KEY.<account>`
+    /// - `$XDG_CONFIG_HOME/aoc/session`, or `.../aoc/session.<account>`, with
+    ///   `~/.config` when `XDG_CONFIG_HOME` isn't set
+    pub fn find(account: Option<&str>) -> Res<Self> {
+        let var = env_var_name(account);
+        if let Ok(token) = std::env::var(&var) {
+            return Self::new(&token, TokenSource::Env(var));
+        }
+
+        let mut searched = vec![format!("${var}")];
+        let files = [Some(project_file(account)), user_file(account)];
+        for path in files.into_iter().flatten() {
+            match std::fs::read_to_string(&path) {
+                Ok(token) => return Self::new(&token, TokenSource::File(path)),
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    searched.push(path.display().to_string())
+                }
+                Err(source) => return Err(AocError::session_io(&path, source)),
+            }
+        }
+        Err(AocError::no_session(searched.join(", ")))
+    }
+
+    /// Accepts the token by itself or copied along with its cookie name.
+    fn new(token: &str, source: TokenSource) -> Res<Self> {
+        let token = token.trim();
+        let token = token.strip_prefix("session=").unwrap_or(token);
+        if token.is_empty() || token.contains(|c: char| c.is_whitespace() || c == ';') {
+            return Err(AocError::invalid_session(source.to_string()));
+        }
+        Ok(Self {
+            token: token.to_string(),
+            source,
+        })
+    }
+
+    /// The error for a response showing that this token wasn't accepted.
+    pub fn expired(&self) -> AocError {
+        AocError::session_expired(self.source.to_string())
+    }
+}
+
+fn env_var_name(account: Option<&str>) -> String {
+    match account {
+        Some(account) => {
+            let suffix: String = account
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_uppercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{SESSION_VAR}_{suffix}")
+        }
+        None => SESSION_VAR.to_string(),
+    }
+}
+
+fn file_name(base: &str, account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!("{base}.{account}"),
+        None => base.to_string(),
+    }
+}
+
+fn project_file(account: Option<&str>) -> PathBuf {
+    Path::new(".").join(file_name(PROJECT_FILE, account))
+}
+
+fn user_file(account: Option<&str>) -> Option<PathBuf> {
+    Some(user_config_dir()?.join(file_name("session", account)))
+}
+
+/// The name shown for the logged in user on any page of the site, or `None`
+/// if the page was fetched without a working session.
+pub fn logged_in_user(html: &str) -> Option<&str> {
+    static USER: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"<div class="user">([^<]*)"#).unwrap());
+    Some(USER.captures(html)?.get(1)?.as_str().trim())
+}
+
+/// Whether an error response means the request wasn't logged in, like when
+/// fetching an input without a working session.
+pub fn is_logged_out(status: StatusCode, body: &str) -> bool {
+    status.is_client_error() && body.to_lowercase().contains("log in")
+}
+
+#[test]
+fn session_t() {
+    assert_eq!(env_var_name(None), "AOC_SESSION");
+    assert_eq!(env_var_name(Some("bob-2")), "AOC_SESSION_BOB_2");
+    assert_eq!(
+        project_file(Some("bob")),
+        Path::new(".").join("API_KEY.bob")
+    );
+
+    let source = || TokenSource::Env(SESSION_VAR.to_string());
+    assert_eq!(Session::new("abc123\n", source()).unwrap().token, "abc123");
+    assert_eq!(
+        Session::new("session=abc123", source()).unwrap().token,
+        "abc123"
+    );
+    assert!(Session::new("  \n", source()).is_err());
+    assert!(Session::new("abc; other=1", source()).is_err());
+
+    let page = r#"<header><div class="user">Some Person <span class="star-count">12*</span></div></header>"#;
+    assert_eq!(logged_in_user(page), Some("Some Person"));
+    assert_eq!(logged_in_user("<a href=\"/auth/login\">[Log In]</a>"), None);
+
+    let body = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+    assert!(is_logged_out(StatusCode::BAD_REQUEST, body));
+    assert!(!is_logged_out(StatusCode::NOT_FOUND, "404 Not Found"));
+}
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use helpers::{Rng, rng};
+
+/// Number of bootstrap resamples used for the confidence interval.
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// Fewest resamples used, for benches with so many samples that
+/// [`BOOTSTRAP_RESAMPLES`] would pass [`BOOTSTRAP_MAX_DRAWS`].
+const BOOTSTRAP_MIN_RESAMPLES: usize = 100;
+
+/// Upper bound on samples drawn while bootstrapping, so benches with millions
+/// of iterations don't take longer to analyze than to run.
+const BOOTSTRAP_MAX_DRAWS: usize = 50_000_000;
+
+/// Confidence level of [`Stats::ci`].
+pub const CONFIDENCE: f64 = 0.95;
+
+/// Summary of a set of benchmark samples. Nothing is removed before computing
+/// these; outliers are only counted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    /// Median absolute deviation from the median.
+    pub mad: Duration,
+    /// Bootstrap confidence interval for the mean.
+    pub ci: (Duration, Duration),
+    pub outliers: Outliers,
+}
+
+/// Outlier counts using Tukey's fences: mild outliers are more than 1.5 IQR
+/// outside the quartiles, and severe outliers are more than 3 IQR outside.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    pub fn mild(&self) -> usize {
+        self.low_mild + self.high_mild
+    }
+
+    pub fn severe(&self) -> usize {
+        self.low_severe + self.high_severe
+    }
+
+    pub fn total(&self) -> usize {
+        self.mild() + self.severe()
+    }
+}
+
+impl Stats {
+    /// Computes statistics for the samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` is empty.
+    pub fn new(times: &[Duration]) -> Self {
+        assert!(!times.is_empty(), "no samples to compute statistics for");
+
+        let mut sorted: Vec<f64> = times.iter().map(|t| t.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+        let n = sorted.len() as f64;
+
+        let mean = sorted.iter().sum::<f64>() / n;
+        let stddev = if sorted.len() > 1 {
+            (sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        let median = percentile(&sorted, 0.5);
+        let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+        deviations.sort_unstable_by(f64::total_cmp);
+        let mad = percentile(&deviations, 0.5);
+
+        Self {
+            samples: sorted.len(),
+            min: nanos(sorted[0]),
+            max: nanos(sorted[sorted.len() - 1]),
+            mean: nanos(mean),
+            median: nanos(median),
+            stddev: nanos(stddev),
+            mad: nanos(mad),
+            ci: bootstrap_mean_ci(&sorted),
+            outliers: classify_outliers(&sorted),
+        }
+    }
+}
+
+/// Linearly interpolated percentile of sorted data, with `p` in `0.0..=1.0`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let low = rank.floor() as usize;
+    let high = rank.ceil() as usize;
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+}
+
+fn classify_outliers(sorted: &[f64]) -> Outliers {
+    let q1 = percentile(sorted, 0.25);
+    let q3 = percentile(sorted, 0.75);
+    let iqr = q3 - q1;
+    let (mild_low, mild_high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+    let (severe_low, severe_high) = (q1 - 3.0 * iqr, q3 + 3.0 * iqr);
+
+    let mut outliers = Outliers::default();
+    for &x in sorted {
+        if x < severe_low {
+            outliers.low_severe += 1;
+        } else if x < mild_low {
+            outliers.low_mild += 1;
+        } else if x > severe_high {
+            outliers.high_severe += 1;
+        } else if x > mild_high {
+            outliers.high_mild += 1;
+        }
+    }
+    outliers
+}
+
+/// Percentile bootstrap of the mean. Uses a fixed seed so the same samples
+/// always give the same interval.
+fn bootstrap_mean_ci(samples: &[f64]) -> (Duration, Duration) {
+    if samples.len() == 1 {
+        let x = nanos(samples[0]);
+        return (x, x);
+    }
+
+    let (resamples, draws) = bootstrap_size(samples.len());
+    // Means of smaller resamples spread out more, by the square root of how
+    // much smaller they are, so they're pulled back in to match
+    let scale = (draws as f64 / samples.len() as f64).sqrt();
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let mut rng = rng();
+    let mut means: Vec<f64> = (0..resamples)
+        .map(|_| {
+            let sum: f64 = (0..draws)
+                .map(|_| samples[rng.random_range(0..samples.len())])
+                .sum();
+            mean + (sum / draws as f64 - mean) * scale
+        })
+        .collect();
+    means.sort_unstable_by(f64::total_cmp);
+
+    let tail = (1.0 - CONFIDENCE) / 2.0;
+    (
+        nanos(percentile(&means, tail)),
+        nanos(percentile(&means, 1.0 - tail)),
+    )
+}
+
+/// How many resamples to take and how many samples to draw for each, keeping
+/// the total under [`BOOTSTRAP_MAX_DRAWS`]. Resamples are as big as the
+/// samples until even the fewest resamples would go over.
+fn bootstrap_size(samples: usize) -> (usize, usize) {
+    let draws = samples.min(BOOTSTRAP_MAX_DRAWS / BOOTSTRAP_MIN_RESAMPLES);
+    let resamples = (BOOTSTRAP_MAX_DRAWS / draws).min(BOOTSTRAP_RESAMPLES);
+    (resamples, draws)
+}
+
+fn nanos(n: f64) -> Duration {
+    Duration::from_nanos(n.round() as u64)
+}
+
+#[test]
+fn stats_t() {
+    let mut times: Vec<Duration> = (1..=9).map(Duration::from_micros).collect();
+    times.push(Duration::from_micros(100));
+    let stats = Stats::new(&times);
+
+    assert_eq!(stats.samples, 10);
+    assert_eq!(stats.min, Duration::from_micros(1));
+    assert_eq!(stats.max, Duration::from_micros(100));
+    assert_eq!(stats.mean, Duration::from_micros(145) / 10);
+    assert_eq!(stats.median, Duration::from_nanos(5500));
+    assert_eq!(stats.mad, Duration::from_nanos(2500));
+    assert_eq!(
+        stats.outliers,
+        Outliers {
+            high_severe: 1,
+            ..Default::default()
+        }
+    );
+    assert!(stats.ci.0 <= stats.mean && stats.mean <= stats.ci.1);
+
+    for samples in [2, 1000, 50_000, 200_000, 500_000, 600_000, 10_000_000] {
+        let (resamples, draws) = bootstrap_size(samples);
+        assert!(
+            resamples * draws <= BOOTSTRAP_MAX_DRAWS,
+            "{samples} samples"
+        );
+        assert!(resamples >= BOOTSTRAP_MIN_RESAMPLES, "{samples} samples");
+    }
+    assert_eq!(bootstrap_size(1000), (BOOTSTRAP_RESAMPLES, 1000));
+}
@@ -0,0 +1,277 @@
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+use chrono::{SecondsFormat, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::http::Client;
+use crate::session::{Session, is_logged_out};
+use crate::{AocError, Res};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Another answer was submitted too recently.
+    RateLimited {
+        wait_secs: Option<u64>,
+    },
+    /// The part was already solved, or isn't unlocked yet.
+    WrongLevel,
+    /// The response didn't say anything recognizable.
+    Unknown,
+}
+
+impl Outcome {
+    /// Reads the outcome from the page returned after submitting.
+    pub fn from_response(html: &str) -> Self {
+        let text = article(html).to_lowercase();
+        if text.contains("that's the right answer") {
+            Outcome::Correct
+        } else if text.contains("you gave an answer too recently") {
+            Outcome::RateLimited {
+                wait_secs: wait_secs(&text),
+            }
+        } else if text.contains("that's not the right answer") {
+            if text.contains("too high") {
+                Outcome::TooHigh
+            } else if text.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if text.contains("you don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// Whether this answer is known to be wrong, so it shouldn't be sent
+    /// again.
+    fn is_wrong(self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::RateLimited {
+                wait_secs: Some(secs),
+            } => write!(f, "submitted too recently, wait {secs}s"),
+            Outcome::RateLimited { wait_secs: None } => write!(f, "submitted too recently"),
+            Outcome::WrongLevel => write!(f, "part already solved or not unlocked yet"),
+            Outcome::Unknown => write!(f, "unrecognized response"),
+        }
+    }
+}
+
+/// The main text of the page, or the whole page if there's no `<article>`.
+fn article(html: &str) -> &str {
+    let Some(start) = html.find("<article") else {
+        return html;
+    };
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |end| start + end);
+    &html[start..end]
+}
+
+/// Reads the wait from text like "you have 1m 5s left to wait".
+fn wait_secs(text: &str) -> Option<u64> {
+    static WAIT: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(?:(\d+)m )?(\d+)s left to wait").unwrap());
+    let captures = WAIT.captures(text)?;
+    let minutes: u64 = captures
+        .get(1)
+        .map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let seconds: u64 = captures[2].parse().ok()?;
+    Some(minutes * 60 + seconds)
+}
+
+/// One submitted answer, as stored in the submissions file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attempt {
+    pub part: u32,
+    pub answer: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    pub timestamp: String,
+}
+
+impl Attempt {
+    pub fn new(part: u32, answer: &str, outcome: Outcome) -> Self {
+        Self {
+            part,
+            answer: answer.to_string(),
+            outcome,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+/// The first part without a correct attempt. Part 1 is always unlocked, so
+/// the site saying it's the wrong level means it was solved some other way,
+/// like in the browser.
+pub fn next_part(attempts: &[Attempt]) -> u32 {
+    let solved = attempts
+        .iter()
+        .any(|a| a.part == 1 && matches!(a.outcome, Outcome::Correct | Outcome::WrongLevel));
+    if solved { 2 } else { 1 }
+}
+
+/// Refuses answers that earlier attempts show can't be right: the part is
+/// already solved, the same answer was wrong, or a number is past a bound from
+/// a too-high or too-low answer. Part 1 counts as solved after a wrong-level
+/// response, like in [`next_part`].
+pub fn check_attempt(attempts: &[Attempt], part: u32, answer: &str) -> Res<()> {
+    if answer.is_empty() {
+        return Err(AocError::submit_refused("the answer is empty"));
+    }
+
+    let number: Option<i128> = answer.parse().ok();
+    for attempt in attempts.iter().filter(|a| a.part == part) {
+        if attempt.outcome == Outcome::Correct {
+            return Err(AocError::submit_refused(format!(
+                "part {part} was already solved with {:?}",
+                attempt.answer
+            )));
+        }
+        if part == 1 && attempt.outcome == Outcome::WrongLevel {
+            return Err(AocError::submit_refused(
+                "part 1 was already solved, since the site said it was the wrong level",
+            ));
+        }
+        if attempt.outcome.is_wrong() && attempt.answer == answer {
+            return Err(AocError::submit_refused(format!(
+                "{answer:?} was already submitted and was {}",
+                attempt.outcome
+            )));
+        }
+
+        let (Some(number), Ok(bound)) = (number, attempt.answer.parse::<i128>()) else {
+            continue;
+        };
+        if attempt.outcome == Outcome::TooHigh && number >= bound {
+            return Err(AocError::submit_refused(format!(
+                "{number} is not lower than {bound}, which was too high"
+            )));
+        }
+        if attempt.outcome == Outcome::TooLow && number <= bound {
+            return Err(AocError::submit_refused(format!(
+                "{number} is not higher than {bound}, which was too low"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Posts an answer and reads the outcome from the response.
+pub fn post_answer(
+    client: &Client,
+    base_url: &str,
+    year: u32,
+    day: u32,
+    part: u32,
+    answer: &str,
+    session: &Session,
+) -> Res<Outcome> {
+    let url = format!("{base_url}/{year}/day/{day}/answer");
+    let level = part.to_string();
+    let mut response = client.post_form(
+        &url,
+        &session.token,
+        [("level", level.as_str()), ("answer", answer)],
+    )?;
+    let body = response.body_mut().read_to_string()?;
+    if !response.status().is_success() {
+        if is_logged_out(response.status(), &body) {
+            return Err(session.expired());
+        }
+        return Err(AocError::submit_response(response.status(), body));
+    }
+    Ok(Outcome::from_response(&body))
+}
+
+#[test]
+fn outcome_t() {
+    let page = |text: &str| format!("<html><main><article><p>{text}</p></article></main></html>");
+
+    assert_eq!(
+        Outcome::from_response(&page(
+            "That's the right answer! You are one gold star closer."
+        )),
+        Outcome::Correct
+    );
+    assert_eq!(
+        Outcome::from_response(&page(
+            "That's not the right answer; your answer is too high.  If you're stuck..."
+        )),
+        Outcome::TooHigh
+    );
+    assert_eq!(
+        Outcome::from_response(&page(
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 1m 5s left to wait."
+        )),
+        Outcome::RateLimited {
+            wait_secs: Some(65)
+        }
+    );
+
+    let attempts = [
+        Attempt::new(1, "100", Outcome::TooHigh),
+        Attempt::new(1, "10", Outcome::TooLow),
+        Attempt::new(1, "50", Outcome::Wrong),
+    ];
+    assert!(check_attempt(&attempts, 1, "100").is_err());
+    assert!(check_attempt(&attempts, 1, "150").is_err());
+    assert!(check_attempt(&attempts, 1, "5").is_err());
+    assert!(check_attempt(&attempts, 1, "50").is_err());
+    assert!(check_attempt(&attempts, 1, "42").is_ok());
+    assert!(check_attempt(&attempts, 2, "150").is_ok());
+    assert_eq!(next_part(&attempts), 1);
+
+    // Both agree that part 1 is done after a wrong-level response
+    let attempts = [Attempt::new(1, "42", Outcome::WrongLevel)];
+    assert_eq!(next_part(&attempts), 2);
+    assert!(check_attempt(&attempts, 1, "43").is_err());
+    assert!(check_attempt(&attempts, 2, "43").is_ok());
+    let attempts = [Attempt::new(2, "42", Outcome::WrongLevel)];
+    assert!(check_attempt(&attempts, 2, "43").is_ok());
+}
+
+#[test]
+fn post_answer_t() {
+    use crate::http::{serve, test_options};
+
+    let page = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+    let (base_url, server) = serve(vec![format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+        page.len()
+    )]);
+
+    let client = test_options().client().unwrap();
+    let session = Session {
+        token: "abc".to_string(),
+        source: crate::session::TokenSource::Env("AOC_SESSION".to_string()),
+    };
+    let outcome = post_answer(&client, &base_url, 2025, 5, 2, "1234", &session).unwrap();
+    assert_eq!(outcome, Outcome::TooLow);
+
+    let requests = server.join().unwrap();
+    let (head, body) = requests[0].split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("POST /2025/day/5/answer "));
+    assert!(head.contains("session=abc"));
+    assert_eq!(body, "level=2&answer=1234");
+}
@@ -0,0 +1,176 @@
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::Res;
+
+/// Which inputs `--test` selects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tests {
+    /// A single input, where 0 is the real input.
+    One(u8),
+    /// Every test input saved for the day.
+    All,
+    /// Test inputs from a list of numbers and ranges, like `1,3-5`.
+    List(Vec<u8>),
+}
+
+impl Tests {
+    /// Whether more than one input is run for each day.
+    pub fn is_many(&self) -> bool {
+        !matches!(self, Tests::One(_))
+    }
+}
+
+pub fn parse_tests(tests: &str) -> Result<Tests, String> {
+    if tests == "all" {
+        return Ok(Tests::All);
+    }
+    if let Ok(test) = tests.parse() {
+        return Ok(Tests::One(test));
+    }
+
+    let num = |n: &str| {
+        n.parse::<u8>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("`{n}` is not a test number"))
+    };
+    let mut list = Vec::new();
+    for item in tests.split(',') {
+        match item.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (num(first)?, num(last)?);
+                if first > last {
+                    return Err(format!("range `{item}` has no tests"));
+                }
+                list.extend(first..=last);
+            }
+            None => list.push(num(item)?),
+        }
+    }
+    list.sort_unstable();
+    list.dedup();
+    Ok(Tests::List(list))
+}
+
+/// Numbers of the `inputNN.txt` files in `dir`, in order.
+pub fn saved_tests(dir: &Path) -> Res<Vec<u8>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut tests = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name();
+        let test = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("input")?.strip_suffix(".txt"))
+            .filter(|n| n.len() == 2)
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|&n| n > 0);
+        tests.extend(test);
+    }
+    tests.sort_unstable();
+    Ok(tests)
+}
+
+/// How one part did on one test input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestResult {
+    Correct,
+    /// Holds the saved answer.
+    Wrong(String),
+    /// There's no saved answer to compare to.
+    Unchecked,
+    /// Holds the panic message.
+    Panicked(String),
+}
+
+impl TestResult {
+    pub fn new(answer: &str, expected: Option<&str>) -> Self {
+        match expected {
+            Some(expected) if expected == answer => TestResult::Correct,
+            Some(expected) => TestResult::Wrong(expected.to_string()),
+            None => TestResult::Unchecked,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            TestResult::Correct => "ok",
+            TestResult::Wrong(_) => "WRONG",
+            TestResult::Unchecked => "no answer",
+            TestResult::Panicked(_) => "PANICKED",
+        }
+    }
+}
+
+/// A row of the summary printed after running several test inputs.
+#[derive(Debug, Clone)]
+pub struct TestRow {
+    pub day: u32,
+    pub test: u8,
+    pub part: u32,
+    pub time: Duration,
+    pub answer: String,
+    pub result: TestResult,
+}
+
+/// Prints the summary table. Answers are left out with `hide_answers`.
+pub fn print_test_table(rows: &[TestRow], hide_answers: bool) {
+    let header = format!(
+        "{:<4} {:<4} {:<4} {:>12}  {:<9}  {}",
+        "day",
+        "test",
+        "part",
+        "time",
+        "result",
+        if hide_answers { "" } else { "answer" }
+    );
+    eprintln!("{}", header.trim_end());
+    for row in rows {
+        let details = match &row.result {
+            TestResult::Panicked(message) => message.clone(),
+            _ if hide_answers => String::new(),
+            TestResult::Wrong(expected) => format!("{:?}, expected {expected:?}", row.answer),
+            _ => format!("{:?}", row.answer),
+        };
+        let line = format!(
+            "{:<4} {:<4} {:<4} {:>12}  {:<9}  {details}",
+            format!("{:02}", row.day),
+            format!("{:02}", row.test),
+            row.part,
+            format!("{:?}", row.time),
+            row.result.label(),
+        );
+        eprintln!("{}", line.trim_end());
+    }
+}
+
+#[test]
+fn parse_tests_t() {
+    assert_eq!(parse_tests("0"), Ok(Tests::One(0)));
+    assert_eq!(parse_tests("3"), Ok(Tests::One(3)));
+    assert_eq!(parse_tests("all"), Ok(Tests::All));
+    assert_eq!(parse_tests("4,1-3,2"), Ok(Tests::List(vec![1, 2, 3, 4])));
+    assert!(parse_tests("3-1").is_err());
+    assert!(parse_tests("0-2").is_err());
+    assert!(parse_tests("1,x").is_err());
+
+    let dir = std::env::temp_dir().join(format!("aoc-saved-tests-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in [
+        "input.txt",
+        "input02.txt",
+        "input01.txt",
+        "answer01.txt",
+        "input1.txt",
+    ] {
+        std::fs::write(dir.join(name), "").unwrap();
+    }
+    assert_eq!(saved_tests(&dir).unwrap(), [1, 2]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(saved_tests(&dir).unwrap().is_empty());
+}
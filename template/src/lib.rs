@@ -0,0 +1,13 @@
+use helpers::*;
+
+pub struct Solver;
+
+impl solver_interface::ChildSolver for Solver {
+    fn part_one(_input: &[u8], _debug: u8) -> impl Display + 'static {
+        0
+    }
+
+    fn part_two(_input: &[u8], _debug: u8) -> impl Display + 'static {
+        0
+    }
+}
@@ -0,0 +1,6 @@
+use helpers::*;
+
+fn main() {
+    use solver_interface::ChildSolverExt;
+    {{crate}}::Solver::run().unwrap_display();
+}